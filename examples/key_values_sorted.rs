@@ -8,13 +8,8 @@ use bevy_input_focus::InputFocus;
 use bevy_ui_text_input::TextInputMode;
 use utils::*;
 
-use std::time::Duration;
-
 use bevy::prelude::*;
-use haalka::{
-    prelude::*,
-    viewport_mutable::{LogicalRect, MutableViewport},
-};
+use haalka::prelude::*;
 
 fn main() {
     App::new()
@@ -140,6 +135,7 @@ fn text_input(
                 .map(BackgroundColor),
         )
         .cursor(CursorIcon::System(SystemCursorIcon::Text))
+        .scroll_into_view_when(focus.signal(), default())
         .on_click(clone!((focus) move || focus.set_neq(true)))
         .on_click_outside_with_system(
             |In((entity, _)), mut input_focus: ResMut<InputFocus>, children: Query<&Children>| {
@@ -171,38 +167,6 @@ fn text_input(
                         focus.set_neq(is_focused);
                     }),
                 )
-                // on focus change, check if the focused element is in view, if not, scroll to it
-                .on_focused_change_with_system(
-                    |In((entity, is_focused)),
-                     child_ofs: Query<&ChildOf>,
-                     mutable_viewports: Query<&MutableViewport>,
-                     logical_rect: LogicalRect| {
-                        if is_focused
-                            && let Some(text_input_rect) = child_ofs
-                                .get(entity)
-                                .ok()
-                                .and_then(|child_of| logical_rect.get(child_of.parent()))
-                        {
-                            for ancestor in child_ofs.iter_ancestors(entity) {
-                                if mutable_viewports.contains(ancestor) {
-                                    if let Some(viewport_rect) = logical_rect.get(ancestor) {
-                                        let d = text_input_rect.min.y - viewport_rect.min.y;
-                                        if d < 0. {
-                                            SCROLL_POSITION.update(|sp| sp + d);
-                                            return;
-                                        }
-                                        let d = text_input_rect.max.y - viewport_rect.max.y;
-                                        if d > 0. {
-                                            SCROLL_POSITION.update(|sp| sp + d);
-                                            return;
-                                        }
-                                    }
-                                    break;
-                                }
-                            }
-                        }
-                    },
-                )
                 .text_signal(string.signal_cloned())
                 .on_change_sync(string),
         )
@@ -514,24 +478,12 @@ fn ui_root() -> impl Element {
                                         },
                                         value: TextInputData::new(""),
                                     });
-                                    async {
-                                        // TODO: need "after rendered" hook to exactly sync when this scroll should be
-                                        // triggered
-                                        sleep(Duration::from_millis(150)).await;
-                                        scroll_to_bottom()
-                                    }
-                                    .apply(spawn)
-                                    .detach();
                                 })
                         }),
                 ),
         )
 }
 
-fn scroll_to_bottom() {
-    SCROLL_POSITION.set(f32::MAX);
-}
-
 fn tabber(keys: Res<ButtonInput<KeyCode>>) {
     // TODO: use .pressed instead of .just_pressed to allow for holding down tab, browser seems to
     // require minimum press time before starting to repeat, and repeating seems slower than refresh
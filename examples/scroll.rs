@@ -42,10 +42,11 @@ fn letter_column(rotate: usize, color: Color) -> impl Element {
         .on_scroll_with_system_disableable_signal(
             BasicScrollHandler::new()
                 .direction(ScrollDirection::Vertical)
-                .pixels(COMPUTED_SIZE)
+                .mode(ScrollMode::SMOOTH)
                 .into_system(),
             signal::or(signal::not(hovered.signal()), SHIFTED.signal()),
         )
+        .scroll_snap(ScrollSnap::Start)
         .with_scroll_position(move |mut scroll_position| scroll_position.offset_y = COMPUTED_SIZE * rotate as f32)
         .hovered_sync(hovered)
         .items(
@@ -74,11 +75,11 @@ fn ui_root() -> impl Element {
                 .on_scroll_with_system_disableable_signal(
                     BasicScrollHandler::new()
                         .direction(ScrollDirection::Horizontal)
-                        // TODO: special handler for auto discrete like rectray https://github.com/mintlu8/bevy-rectray/blob/main/examples/scroll_discrete.rs
-                        .pixels(63.)
+                        .mode(ScrollMode::SMOOTH)
                         .into_system(),
                     signal::not(signal::and(hovered.signal(), SHIFTED.signal())),
                 )
+                .scroll_snap(ScrollSnap::Start)
                 .hovered_sync(hovered)
                 .items(
                     [
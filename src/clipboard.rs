@@ -0,0 +1,98 @@
+//! Pluggable clipboard backing [`TextInput`](super::text_input::TextInput)'s copy, cut, and paste
+//! shortcuts, see [`TextInputClipboard`].
+
+use std::sync::{Arc, Mutex};
+
+use bevy_ecs::prelude::*;
+use bevy_ui_text_input::clipboard;
+use bevy_utils::prelude::*;
+
+/// Storage for text copied and cut from, and pasted into,
+/// [`TextInput`](super::text_input::TextInput)s.
+pub trait Clipboard: Send + Sync + 'static {
+    /// The text on the clipboard, if any.
+    fn get(&mut self) -> Option<String>;
+
+    /// Replace the text on the clipboard.
+    fn set(&mut self, text: String);
+}
+
+/// The [`Clipboard`] used by [`TextInput`](super::text_input::TextInput)s, a [`SystemClipboard`]
+/// by default; insert this [`Resource`] with an [`InMemoryClipboard`] to copy and paste
+/// headlessly, e.g. in tests.
+#[derive(Resource)]
+pub struct TextInputClipboard(Box<dyn Clipboard>);
+
+impl TextInputClipboard {
+    #[allow(missing_docs)]
+    pub fn new(clipboard: impl Clipboard) -> Self {
+        Self(Box::new(clipboard))
+    }
+
+    /// The text on the clipboard, if any.
+    pub fn get(&mut self) -> Option<String> {
+        self.0.get()
+    }
+
+    /// Replace the text on the clipboard.
+    pub fn set(&mut self, text: String) {
+        self.0.set(text)
+    }
+}
+
+impl Default for TextInputClipboard {
+    fn default() -> Self {
+        Self::new(SystemClipboard::default())
+    }
+}
+
+/// The operating system's clipboard, via `bevy_ui_text_input`'s. Falls back to an
+/// [`InMemoryClipboard`] where it is unavailable, like on a display-less server, or can't be read
+/// synchronously, like on the web, where copied text is still written to the browser's clipboard.
+#[derive(Default)]
+pub struct SystemClipboard {
+    system: Option<clipboard::Clipboard>,
+    fallback: InMemoryClipboard,
+}
+
+impl SystemClipboard {
+    fn system(&mut self) -> &mut clipboard::Clipboard {
+        // connect lazily, so apps which never copy or paste don't touch the clipboard
+        self.system.get_or_insert_with(default)
+    }
+}
+
+impl Clipboard for SystemClipboard {
+    fn get(&mut self) -> Option<String> {
+        // reading the browser's clipboard is asynchronous and asks for permission
+        #[cfg(not(target_arch = "wasm32"))]
+        match self.system().fetch_text().poll_result() {
+            Some(Err(clipboard::ClipboardError::ClipboardNotSupported)) | None => (),
+            result => return result.and_then(Result::ok),
+        }
+        self.fallback.get()
+    }
+
+    fn set(&mut self, text: String) {
+        if self.system().set_text(text.as_str()).is_err() || cfg!(target_arch = "wasm32") {
+            self.fallback.set(text)
+        }
+    }
+}
+
+/// [`Clipboard`] which only holds text within this app. Clones share their text, so a handle kept
+/// aside can inspect and seed the clipboard of a [`TextInputClipboard`].
+#[derive(Clone, Default)]
+pub struct InMemoryClipboard(Arc<Mutex<Option<String>>>);
+
+impl Clipboard for InMemoryClipboard {
+    fn get(&mut self) -> Option<String> {
+        self.0.lock().ok().and_then(|text| text.clone())
+    }
+
+    fn set(&mut self, text: String) {
+        if let Ok(mut current) = self.0.lock() {
+            *current = Some(text);
+        }
+    }
+}
@@ -3,17 +3,19 @@ use bevy_picking::prelude::*;
 use bevy_ui::prelude::*;
 use futures_signals::{
     signal::{Signal, SignalExt},
-    signal_vec::{SignalVec, SignalVecExt},
+    signal_vec::{MutableVec, SignalVec, SignalVecExt},
 };
 
 use super::{
     align::{AddRemove, AlignHolder, Alignable, Aligner, Alignment, ChildAlignable},
+    draggable::{Draggable, DropTarget, reorderable_items},
     element::{IntoOptionElement, Nameable, UiRootable},
     global_event_aware::GlobalEventAware,
     mouse_wheel_scrollable::MouseWheelScrollable,
     pointer_event_aware::{CursorOnHoverable, PointerEventAware},
     raw::{RawElWrapper, RawHaalkaEl},
-    viewport_mutable::ViewportMutable,
+    tooltip::Tooltipable,
+    viewport_mutable::{Axis, ScrollIntoViewable, Stickable, ViewportMutable},
 };
 
 /// [`Element`](super::element::Element) with vertically stacked children. Port of [MoonZoon](https://github.com/MoonZoon/MoonZoon)'s [`Column`](https://github.com/MoonZoon/MoonZoon/blob/main/crates/zoon/src/element/column.rs).
@@ -62,11 +64,16 @@ impl<NodeType: Bundle> RawElWrapper for Column<NodeType> {
 }
 
 impl<NodeType: Bundle> CursorOnHoverable for Column<NodeType> {}
+impl<NodeType: Bundle> Draggable for Column<NodeType> {}
+impl<NodeType: Bundle> DropTarget for Column<NodeType> {}
 impl<NodeType: Bundle> GlobalEventAware for Column<NodeType> {}
 impl<NodeType: Bundle> Nameable for Column<NodeType> {}
 impl<NodeType: Bundle> PointerEventAware for Column<NodeType> {}
 impl<NodeType: Bundle> MouseWheelScrollable for Column<NodeType> {}
 impl<NodeType: Bundle> UiRootable for Column<NodeType> {}
+impl<NodeType: Bundle> Tooltipable for Column<NodeType> {}
+impl<NodeType: Bundle> ScrollIntoViewable for Column<NodeType> {}
+impl<NodeType: Bundle> Stickable for Column<NodeType> {}
 impl<NodeType: Bundle> ViewportMutable for Column<NodeType> {}
 
 impl<NodeType: Bundle> Column<NodeType> {
@@ -134,6 +141,19 @@ impl<NodeType: Bundle> Column<NodeType> {
         }
         self
     }
+
+    /// Declare reactive vertically stacked children from a [`MutableVec`], which can be reordered by
+    /// dragging them with the primary pointer button. While an item is dragged, the other items
+    /// shift to leave a gap where it would be dropped; dropping it moves it to that index of the
+    /// [`MutableVec`]. Items are [`Draggable`] with their value as the payload, so they can also
+    /// be dropped onto [`DropTarget`]s.
+    pub fn items_signal_vec_reorderable<T: Clone + Send + Sync + 'static, IOE: IntoOptionElement + 'static>(
+        self,
+        items: MutableVec<T>,
+        item: impl FnMut(T) -> IOE + Send + 'static,
+    ) -> Self {
+        self.items_signal_vec(reorderable_items(items, Axis::Vertical, item))
+    }
 }
 
 impl<NodeType: Bundle> Alignable for Column<NodeType> {
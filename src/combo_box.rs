@@ -0,0 +1,316 @@
+//! Text input which suggests matching items in a dropdown as one types, see [`ComboBox`].
+
+use std::{fmt::Display, sync::Arc};
+
+use apply::Apply;
+use bevy_color::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_input::{ButtonState, keyboard::KeyboardInput, prelude::*};
+use bevy_input_focus::InputFocus;
+use bevy_picking::prelude::*;
+use bevy_tasks::Task;
+use bevy_ui::prelude::*;
+use bevy_ui_text_input::TextInputMode;
+use futures_signals::{
+    map_ref,
+    signal::{BoxSignal, Mutable, ReadOnlyMutable, Signal, SignalExt},
+    signal_vec::{MutableVec, MutableVecLockMut, SignalVec, SignalVecExt},
+};
+use haalka_futures_signals_ext::SignalExtBool;
+
+use super::{
+    column::Column,
+    el::El,
+    element::{ElementWrapper, Nameable, UiRootable},
+    global_event_aware::GlobalEventAware,
+    pointer_event_aware::{CursorOnHoverable, PointerEventAware},
+    raw::RawElWrapper,
+    text_input::TextInput,
+    utils::{clone, spawn},
+    viewport_mutable::{Axis, ScrollIntoViewOptions, ScrollIntoViewable, ViewportMutable},
+};
+
+/// [`GlobalZIndex`] of [`ComboBox`] dropdowns, so they are rendered above everything except
+/// tooltips and dragged elements.
+pub const DROPDOWN_Z_INDEX: i32 = i32::MAX - 3;
+
+/// How a [`ComboBox`]'s text is matched against its suggestions, ignoring case.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub enum ComboBoxMatch {
+    /// Suggestions starting with the text.
+    #[default]
+    Prefix,
+    /// Suggestions containing the text.
+    Substring,
+    /// Suggestions containing the characters of the text in order, though not necessarily
+    /// adjacent, e.g. `"bfl"` matches `"butterfly"`.
+    Fuzzy,
+}
+
+impl ComboBoxMatch {
+    /// Whether `candidate` matches `query`.
+    pub fn matches(self, query: &str, candidate: &str) -> bool {
+        let (query, candidate) = (query.to_lowercase(), candidate.to_lowercase());
+        match self {
+            Self::Prefix => candidate.starts_with(&query),
+            Self::Substring => candidate.contains(&query),
+            Self::Fuzzy => {
+                let mut candidate = candidate.chars();
+                query.chars().all(|c| candidate.any(|candidate_c| candidate_c == c))
+            }
+        }
+    }
+}
+
+type Matcher<T> = Arc<dyn Fn(&str, &T) -> bool + Send + Sync>;
+type ItemBuilder<T> = Arc<dyn Fn(T, BoxSignal<'static, bool>) -> El<Node> + Send + Sync>;
+
+/// [`TextInput`] whose text filters a [`SignalVec`] of suggestions, shown in a dropdown below it
+/// while typing.
+///
+/// The arrow keys move the highlight through the matching suggestions, Enter or clicking selects
+/// one, replacing the text with it, and Escape or clicking outside closes the dropdown.
+pub struct ComboBox<T> {
+    el: El<Node>,
+    text_input: TextInput,
+    dropdown: Column<Node>,
+    suggestions: MutableVec<T>,
+    updater: Task<()>,
+    matcher: Matcher<T>,
+    selected: Mutable<Option<T>>,
+    item: ItemBuilder<T>,
+}
+
+fn default_item<T: Display>(item: T, highlighted: BoxSignal<'static, bool>) -> El<Node> {
+    El::<Node>::new()
+        .with_node(|mut node| {
+            node.width = Val::Percent(100.);
+            node.padding = UiRect::axes(Val::Px(6.), Val::Px(3.));
+        })
+        .background_color_signal(highlighted.map_bool(
+            || BackgroundColor(Color::srgba(1., 1., 1., 0.2)),
+            || BackgroundColor(Color::NONE),
+        ))
+        .child(El::<Text>::new().text(Text::new(item.to_string())))
+}
+
+impl<T: Clone + Display + Send + Sync + 'static> ComboBox<T> {
+    #[allow(missing_docs)]
+    pub fn new(suggestions: impl SignalVec<Item = T> + Send + 'static) -> Self {
+        let mirror = MutableVec::new();
+        let updater = suggestions
+            .for_each(clone!((mirror) move |diff| {
+                MutableVecLockMut::apply_vec_diff(&mut mirror.lock_mut(), diff);
+                async {}
+            }))
+            .apply(spawn);
+        Self {
+            el: El::<Node>::new(),
+            text_input: TextInput::new()
+                .with_node(|mut node| node.width = Val::Percent(100.))
+                .with_text_input_node(|mut text_input_node| text_input_node.mode = TextInputMode::SingleLine),
+            dropdown: Column::<Node>::new()
+                .with_node(|mut node| {
+                    node.position_type = PositionType::Absolute;
+                    node.top = Val::Percent(100.);
+                    node.width = Val::Percent(100.);
+                    node.max_height = Val::Px(200.);
+                })
+                .background_color(BackgroundColor(Color::srgb(0.15, 0.15, 0.15)))
+                .global_z_index(GlobalZIndex(DROPDOWN_Z_INDEX))
+                .mutable_viewport(Axis::Vertical),
+            suggestions: mirror,
+            updater,
+            matcher: Arc::new(|query, item: &T| ComboBoxMatch::default().matches(query, &item.to_string())),
+            selected: Mutable::new(None),
+            item: Arc::new(default_item),
+        }
+    }
+
+    /// Set how the text is matched against the suggestions' [`Display`] text,
+    /// [`ComboBoxMatch::Prefix`] by default.
+    pub fn match_mode(self, match_mode: ComboBoxMatch) -> Self {
+        self.match_with(move |query, item| match_mode.matches(query, &item.to_string()))
+    }
+
+    /// Match the text against the suggestions with a custom function.
+    pub fn match_with(mut self, matcher: impl Fn(&str, &T) -> bool + Send + Sync + 'static) -> Self {
+        self.matcher = Arc::new(matcher);
+        self
+    }
+
+    /// Two-way bind the selected suggestion to a [`Mutable`]; setting it replaces the text, and editing the text
+    /// away from it clears it.
+    pub fn selected_sync(mut self, selected: Mutable<Option<T>>) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// Build each suggestion's [`Element`](super::element::Element) from it and a [`Signal`](futures_signals::signal::Signal)
+    /// of whether it is highlighted.
+    pub fn item(mut self, item: impl Fn(T, BoxSignal<'static, bool>) -> El<Node> + Send + Sync + 'static) -> Self {
+        self.item = Arc::new(item);
+        self
+    }
+
+    /// Modify the [`TextInput`], e.g. to set its font.
+    pub fn text_input(mut self, f: impl FnOnce(TextInput) -> TextInput) -> Self {
+        self.text_input = f(self.text_input);
+        self
+    }
+
+    /// Modify the dropdown [`Column`] containing the matching suggestions.
+    pub fn dropdown(mut self, f: impl FnOnce(Column<Node>) -> Column<Node>) -> Self {
+        self.dropdown = f(self.dropdown);
+        self
+    }
+}
+
+impl<T: Clone + Display + Send + Sync + 'static> ElementWrapper for ComboBox<T> {
+    type EL = El<Node>;
+    fn element_mut(&mut self) -> &mut Self::EL {
+        &mut self.el
+    }
+
+    fn into_el(self) -> Self::EL {
+        let Self {
+            el,
+            text_input,
+            dropdown,
+            suggestions,
+            updater,
+            matcher,
+            selected,
+            item,
+        } = self;
+        let query = Mutable::new(String::new());
+        let open = Mutable::new(false);
+        // index of the highlighted suggestion among the matching ones
+        let highlighted = Mutable::new(None);
+        let matching = clone!((suggestions, query, matcher) move || {
+            let query = query.lock_ref();
+            suggestions.lock_ref().iter().filter(|item| matcher(&query, item)).cloned().collect::<Vec<_>>()
+        });
+        let select = clone!((selected, open, highlighted) move |item: T| {
+            open.set_neq(false);
+            highlighted.set_neq(None);
+            // not `set_neq`, so the text is replaced even if the selection didn't change
+            selected.set(Some(item));
+        });
+        let text_input = text_input
+            .text_signal_forced(selected.signal_cloned().map(|selected_option| selected_option.map(|item| item.to_string())))
+            .on_change(clone!((selected, query, open, highlighted) move |text| {
+                // text replaced by a selection
+                let selected_text = selected.lock_ref().as_ref().map(ToString::to_string);
+                if selected_text.as_ref() != Some(&text) {
+                    // edited away from the selection, which leaves the text be
+                    if selected_text.is_some() {
+                        selected.set(None);
+                    }
+                    highlighted.set_neq(None);
+                    open.set_neq(true);
+                }
+                query.set(text);
+            }))
+            .on_cancel(clone!((open) move || open.set_neq(false)))
+            .on_global_event_with_system::<KeyboardInput, _>(
+                clone!((open, highlighted, select) move |In((entity, keyboard_input)): In<(Entity, KeyboardInput)>, focused_option: Res<InputFocus>| {
+                    if focused_option.0 != Some(entity) || keyboard_input.state != ButtonState::Pressed {
+                        return;
+                    }
+                    let step = match keyboard_input.key_code {
+                        KeyCode::ArrowDown => 1,
+                        KeyCode::ArrowUp => -1,
+                        KeyCode::Enter => {
+                            if open.get()
+                                && let Some(i) = highlighted.get()
+                                && let Some(item) = matching().into_iter().nth(i)
+                            {
+                                select(item);
+                            }
+                            return;
+                        }
+                        _ => return,
+                    };
+                    let len = matching().len() as isize;
+                    if len == 0 {
+                        return;
+                    }
+                    if !open.replace(true) {
+                        highlighted.set(Some(if step > 0 { 0 } else { len as usize - 1 }));
+                        return;
+                    }
+                    let next = match highlighted.get() {
+                        Some(i) => (i as isize + step).rem_euclid(len),
+                        None if step > 0 => 0,
+                        None => len - 1,
+                    };
+                    highlighted.set(Some(next as usize));
+                }),
+            );
+        let dropdown = dropdown.items_signal_vec(
+            suggestions
+                .signal_vec_cloned()
+                .filter_signal_cloned(clone!((query, matcher) move |suggestion| {
+                    query.signal_ref(clone!((matcher, suggestion) move |query| matcher(query, &suggestion)))
+                }))
+                .enumerate()
+                .map(clone!((highlighted, select) move |(index, suggestion)| {
+                    item(suggestion.clone(), is_highlighted(&highlighted, &index).boxed())
+                        .scroll_into_view_when(is_highlighted(&highlighted, &index), ScrollIntoViewOptions::default())
+                        .update_raw_el(|raw_el| raw_el.insert(Pickable::default()))
+                        .on_hovered_change(clone!((highlighted, index) move |hovered| {
+                            if hovered {
+                                highlighted.set_neq(index.get());
+                            }
+                        }))
+                        .on_click(clone!((select) move || select(suggestion.clone())))
+                })),
+        )
+        .on_signal_with_node(open.signal(), |mut node, open| {
+            node.display = if open { bevy_ui::Display::Flex } else { bevy_ui::Display::None }
+        });
+        el.update_raw_el(|raw_el| raw_el.hold_tasks([updater]))
+            .on_click_outside(move || open.set_neq(false))
+            .child(text_input)
+            .child(dropdown)
+    }
+}
+
+fn is_highlighted(
+    highlighted: &Mutable<Option<usize>>,
+    index: &ReadOnlyMutable<Option<usize>>,
+) -> impl Signal<Item = bool> + Send + 'static {
+    map_ref! {
+        let highlighted = highlighted.signal(), let index = index.signal() => {
+            highlighted.is_some() && highlighted == index
+        }
+    }
+    .dedupe()
+}
+
+impl<T: Clone + Display + Send + Sync + 'static> CursorOnHoverable for ComboBox<T> {}
+impl<T: Clone + Display + Send + Sync + 'static> GlobalEventAware for ComboBox<T> {}
+impl<T: Clone + Display + Send + Sync + 'static> Nameable for ComboBox<T> {}
+impl<T: Clone + Display + Send + Sync + 'static> PointerEventAware for ComboBox<T> {}
+impl<T: Clone + Display + Send + Sync + 'static> UiRootable for ComboBox<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_modes_ignore_case() {
+        assert!(ComboBoxMatch::Prefix.matches("But", "butterfly"));
+        assert!(!ComboBoxMatch::Prefix.matches("fly", "butterfly"));
+        assert!(ComboBoxMatch::Substring.matches("FLY", "butterfly"));
+        assert!(!ComboBoxMatch::Substring.matches("bfl", "butterfly"));
+        assert!(ComboBoxMatch::Fuzzy.matches("bfl", "Butterfly"));
+        assert!(!ComboBoxMatch::Fuzzy.matches("flb", "butterfly"));
+        // repeated characters must each be matched
+        assert!(!ComboBoxMatch::Fuzzy.matches("tttt", "butterfly"));
+        for mode in [ComboBoxMatch::Prefix, ComboBoxMatch::Substring, ComboBoxMatch::Fuzzy] {
+            assert!(mode.matches("", "butterfly"));
+        }
+    }
+}
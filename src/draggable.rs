@@ -0,0 +1,863 @@
+//! Semantics for dragging [`Element`](super::element::Element)s and dropping them onto other
+//! [`Element`](super::element::Element)s, see [`Draggable`] and [`DropTarget`].
+
+use std::sync::{Arc, Mutex, OnceLock};
+
+use apply::Apply;
+use bevy_app::prelude::*;
+use bevy_derive::*;
+use bevy_ecs::prelude::*;
+use bevy_math::prelude::*;
+use bevy_picking::{hover::HoverMap, pointer::PointerId, prelude::*};
+use bevy_ui::prelude::*;
+use bevy_winit::cursor::CursorIcon;
+use futures_signals::{
+    map_ref,
+    signal::{Mutable, Signal, SignalExt},
+    signal_vec::{MutableVec, SignalVec, SignalVecExt},
+};
+
+use super::{
+    align::AlignabilityFacade,
+    el::El,
+    element::{IntoElement, IntoOptionElement, TypeEraseable},
+    pointer_event_aware::{CursorPriority, CursorStack, ElementSpace, LocalPointer, PickingIgnoredTree, PointerEventAware},
+    raw::{RawElWrapper, RawElement, RawHaalkaEl, observe, register_system, utils::remove_system_holder_on_remove},
+    utils::clone,
+    viewport_mutable::Axis,
+};
+
+/// [`GlobalZIndex`] of dragged elements and drag ghosts, so they are rendered above everything
+/// else.
+pub const DRAG_Z_INDEX: i32 = i32::MAX - 1;
+
+/// [`Component`] holding the payload of a [`Draggable`] element, which is handed to
+/// [`DropTarget`] handlers when the element is dragged over or dropped onto them.
+#[derive(Component, Clone)]
+pub struct DragPayload<T>(pub T);
+
+/// Limits on how far a [`Draggable`] element (or its ghost) can move while being dragged.
+#[derive(Clone, Copy, Debug)]
+pub enum DragBounds {
+    /// Keep the dragged element within its parent's bounds.
+    Parent,
+    /// Keep the dragged element's offset from where the drag started within this [`Rect`], in
+    /// logical pixels.
+    Offset(Rect),
+}
+
+#[derive(Component, Clone, Copy)]
+struct DragAxis(Axis);
+
+#[derive(Component, Clone, Copy)]
+struct DragBoundsConstraint(DragBounds);
+
+#[derive(Component, Clone)]
+struct DragGhost(Arc<dyn Fn() -> AlignabilityFacade + Send + Sync>);
+
+#[derive(Component, Clone)]
+struct DragCursor(Option<CursorIcon>);
+
+#[derive(Component)]
+struct DraggableRegistered;
+
+/// Present on [`Draggable`] elements while they are being dragged.
+#[derive(Component)]
+struct DragState {
+    // the element's rect when the drag started, in logical pixels
+    origin: Rect,
+    // the allowed range of the offset, if bounded
+    range: Option<Rect>,
+    offset: Vec2,
+    // latest pointer position, in logical window pixels
+    position: Vec2,
+    // the pointer dragging the element
+    pointer_id: PointerId,
+    // the `left` and `top` the layout applied when the drag started, in logical pixels
+    start: Vec2,
+    // restored when the drag ends
+    left: Val,
+    top: Val,
+    pickable: Option<Pickable>,
+    global_z_index: Option<GlobalZIndex>,
+    ghost: Option<Entity>,
+}
+
+#[derive(Event, Deref)]
+struct DraggingChange(bool);
+
+/// Enables dragging an [`Element`](super::element::Element) with the primary pointer button and
+/// carrying a payload to [`DropTarget`]s.
+pub trait Draggable: PointerEventAware {
+    /// Make this element draggable with the primary pointer button, carrying the `payload` to any
+    /// [`DropTarget`]s it is dragged over or dropped onto. Unless a
+    /// [`.drag_ghost`](Draggable::drag_ghost) is set, the element itself follows the pointer and
+    /// returns to its place in the layout when the drag ends; move the data backing the element in
+    /// a [`DropTarget`] handler to move it for good.
+    ///
+    /// # Notes
+    /// While being dragged, this element does not block picking of the elements under it so
+    /// [`DropTarget`]s can be hovered, but its descendants still do unless they are
+    /// [`Pickable::IGNORE`].
+    fn draggable<T: Clone + Send + Sync + 'static>(self, payload: T) -> Self {
+        self.update_raw_el(|raw_el| raw_el.insert(DragPayload(payload)).apply(register_draggable))
+    }
+
+    /// Make this element draggable with the primary pointer button, reactively setting the
+    /// payload it carries to [`DropTarget`]s with a [`Signal`]. See
+    /// [`.draggable`](Draggable::draggable).
+    fn draggable_signal<T: Clone + Send + Sync + 'static, S: Signal<Item = T> + Send + 'static>(
+        self,
+        payload_signal: S,
+    ) -> Self {
+        self.update_raw_el(|raw_el| {
+            raw_el
+                .component_signal::<DragPayload<T>, _>(payload_signal.map(DragPayload))
+                .apply(register_draggable)
+        })
+    }
+
+    /// Instead of moving this element while it is dragged, spawn the element returned by `ghost`
+    /// at this element's position and move it instead; the ghost is despawned when the drag ends.
+    fn drag_ghost<IE: IntoElement + 'static>(self, ghost: impl Fn() -> IE + Send + Sync + 'static) -> Self {
+        self.update_raw_el(|raw_el| raw_el.insert(DragGhost(Arc::new(move || ghost().into_element().type_erase()))))
+    }
+
+    /// Constrain the movement of this element while it is dragged to an [`Axis`].
+    fn drag_axis(mut self, axis_option: impl Into<Option<Axis>>) -> Self {
+        if let Some(axis) = axis_option.into() {
+            self = self.update_raw_el(|raw_el| raw_el.insert(DragAxis(axis)));
+        }
+        self
+    }
+
+    /// Constrain how far this element can move while it is dragged, see [`DragBounds`].
+    fn drag_bounds(mut self, bounds_option: impl Into<Option<DragBounds>>) -> Self {
+        if let Some(bounds) = bounds_option.into() {
+            self = self.update_raw_el(|raw_el| raw_el.insert(DragBoundsConstraint(bounds)));
+        }
+        self
+    }
+
+    /// While this element is dragged, set the cursor to [`Some`] [`CursorIcon`] or hide it if
+    /// [`None`], overriding the cursors of hovered elements, see [`CursorPriority::DRAG`].
+    fn drag_cursor(self, cursor_option: impl Into<Option<CursorIcon>>) -> Self {
+        let cursor_option = cursor_option.into();
+        self.update_raw_el(|raw_el| {
+            raw_el.insert(DragCursor(cursor_option)).on_remove(|world, entity| {
+                if let Some(mut cursor_stack) = world.get_resource_mut::<CursorStack>() {
+                    cursor_stack.remove(entity, CursorPriority::DRAG);
+                }
+            })
+        })
+    }
+
+    /// When this element's dragging state changes, run a [`System`] which takes
+    /// [`In`](`System::In`) this element's [`Entity`] and its current dragging state. This method
+    /// can be called repeatedly to register many such handlers.
+    fn on_dragging_change_with_system<Marker>(
+        self,
+        handler: impl IntoSystem<In<(Entity, bool)>, (), Marker> + Send + 'static,
+    ) -> Self {
+        self.update_raw_el(|raw_el| {
+            let system_holder = Arc::new(OnceLock::new());
+            raw_el
+                .on_spawn(clone!((system_holder) move |world, entity| {
+                    let system = register_system(world, handler);
+                    let _ = system_holder.set(system);
+                    observe(world, entity, move |change: Trigger<DraggingChange>, mut commands: Commands| {
+                        commands.run_system_with(system, (change.target(), **change.event()));
+                    });
+                }))
+                .apply(remove_system_holder_on_remove(system_holder))
+        })
+    }
+
+    /// When this element's dragging state changes, run a function with its current dragging
+    /// state.
+    fn on_dragging_change(self, mut handler: impl FnMut(bool) + Send + Sync + 'static) -> Self {
+        self.on_dragging_change_with_system(move |In((_, dragging))| handler(dragging))
+    }
+
+    /// Sync a [`Mutable`] with this element's dragging state.
+    fn dragging_sync(self, dragging: Mutable<bool>) -> Self {
+        self.on_dragging_change(move |is_dragging| dragging.set_neq(is_dragging))
+    }
+}
+
+fn register_draggable(raw_el: RawHaalkaEl) -> RawHaalkaEl {
+    raw_el.insert(Pickable::default()).on_spawn(|world, entity| {
+        if let Ok(mut entity) = world.get_entity_mut(entity) {
+            if entity.contains::<DraggableRegistered>() {
+                return;
+            }
+            entity.insert(DraggableRegistered);
+        }
+        observe(world, entity, start_drag);
+        observe(world, entity, drag);
+        observe(world, entity, end_drag);
+    })
+}
+
+#[allow(clippy::type_complexity)]
+fn start_drag(
+    mut drag_start: Trigger<Pointer<DragStart>>,
+    element_space: ElementSpace,
+    child_ofs: Query<&ChildOf>,
+    computed_nodes: Query<(&ComputedNode, Option<&ComputedNodeTarget>)>,
+    draggables: Query<(
+        &Node,
+        Option<&Pickable>,
+        Option<&GlobalZIndex>,
+        Option<&DragBoundsConstraint>,
+        Option<&DragGhost>,
+        Option<&DragCursor>,
+    )>,
+    mut cursor_stack: ResMut<CursorStack>,
+    mut commands: Commands,
+) {
+    if !matches!(drag_start.button, PointerButton::Primary) {
+        return;
+    }
+    drag_start.propagate(false);
+    let entity = drag_start.target();
+    let (Some(origin), Ok((node, pickable, global_z_index, bounds, ghost, cursor))) =
+        (element_space.rect(entity), draggables.get(entity))
+    else {
+        return;
+    };
+    let range = bounds.and_then(|&DragBoundsConstraint(bounds)| match bounds {
+        DragBounds::Parent => child_ofs
+            .get(entity)
+            .ok()
+            .and_then(|&ChildOf(parent)| element_space.rect(parent))
+            .map(|parent| {
+                let (min, max) = (parent.min - origin.min, parent.max - origin.max);
+                // along axes the element is bigger than its parent, it can't move at all
+                let fits = min.cmple(max);
+                Rect {
+                    min: Vec2::select(fits, min, Vec2::ZERO),
+                    max: Vec2::select(fits, max, Vec2::ZERO),
+                }
+            }),
+        DragBounds::Offset(rect) => Some(rect),
+    });
+    let parent_option = child_ofs.get(entity).ok().and_then(|&ChildOf(parent)| {
+        let (computed_node, _) = computed_nodes.get(parent).ok()?;
+        Some((element_space.rect(parent)?, computed_node))
+    });
+    // `ComputedNode`s are in physical pixels
+    let (containing_min, content_size) = parent_option
+        .map(|(rect, computed_node)| {
+            let inverse_scale_factor = computed_node.inverse_scale_factor();
+            let (border, inset) = (computed_node.border(), computed_node.content_inset());
+            (
+                rect.min + Vec2::new(border.left, border.top) * inverse_scale_factor,
+                rect.size() - Vec2::new(inset.left + inset.right, inset.top + inset.bottom) * inverse_scale_factor,
+            )
+        })
+        .unwrap_or_default();
+    let viewport_size = computed_nodes
+        .get(entity)
+        .ok()
+        .and_then(|(_, target)| target)
+        .map(ComputedNodeTarget::logical_size)
+        .unwrap_or_default();
+    let layout = origin.min - containing_min;
+    let absolute = node.position_type == PositionType::Absolute;
+    let start = Vec2::new(
+        resolved_inset(node.left, absolute, layout.x, content_size.x, viewport_size),
+        resolved_inset(node.top, absolute, layout.y, content_size.y, viewport_size),
+    );
+    let ghost = ghost.map(|DragGhost(ghost)| {
+        let ghost = ghost.clone();
+        let ghost_entity = commands.spawn_empty().id();
+        commands.queue(move |world: &mut World| {
+            El::<Node>::new()
+                .with_node(move |mut node| {
+                    node.position_type = PositionType::Absolute;
+                    node.left = Val::Px(origin.min.x);
+                    node.top = Val::Px(origin.min.y);
+                })
+                .global_z_index(GlobalZIndex(DRAG_Z_INDEX))
+                // so the drop targets under the pointer can be hovered
+                .update_raw_el(|raw_el| raw_el.insert(PickingIgnoredTree))
+                .child(ghost())
+                .into_raw()
+                .into_node_builder()
+                .spawn_on_entity(world, ghost_entity);
+        });
+        ghost_entity
+    });
+    if let Some(DragCursor(cursor)) = cursor {
+        cursor_stack.push(entity, CursorPriority::DRAG, cursor.clone());
+    }
+    if let Ok(mut entity) = commands.get_entity(entity) {
+        entity.try_insert((
+            DragState {
+                origin,
+                range,
+                offset: Vec2::ZERO,
+                position: drag_start.pointer_location.position,
+                pointer_id: drag_start.pointer_id,
+                start,
+                left: node.left,
+                top: node.top,
+                pickable: pickable.cloned(),
+                global_z_index: global_z_index.copied(),
+                ghost,
+            },
+            Pickable {
+                should_block_lower: false,
+                is_hoverable: true,
+            },
+        ));
+        if ghost.is_none() {
+            entity.try_insert(GlobalZIndex(DRAG_Z_INDEX));
+        }
+    }
+    commands.trigger_targets(DraggingChange(true), entity);
+}
+
+fn drag(
+    mut drag: Trigger<Pointer<Drag>>,
+    ui_scale: Res<UiScale>,
+    mut drag_states: Query<(&mut DragState, Option<&DragAxis>)>,
+    mut nodes: Query<&mut Node>,
+) {
+    if !matches!(drag.button, PointerButton::Primary) {
+        return;
+    }
+    drag.propagate(false);
+    let entity = drag.target();
+    let Ok((mut drag_state, axis)) = drag_states.get_mut(entity) else {
+        return;
+    };
+    let mut offset = drag.distance / ui_scale.0;
+    match axis {
+        Some(DragAxis(Axis::Horizontal)) => offset.y = 0.,
+        Some(DragAxis(Axis::Vertical)) => offset.x = 0.,
+        _ => {}
+    }
+    if let Some(range) = drag_state.range {
+        offset = offset.clamp(range.min, range.max);
+    }
+    drag_state.offset = offset;
+    drag_state.position = drag.pointer_location.position;
+    if let Some(ghost) = drag_state.ghost {
+        if let Ok(mut node) = nodes.get_mut(ghost) {
+            node.left = Val::Px(drag_state.origin.min.x + offset.x);
+            node.top = Val::Px(drag_state.origin.min.y + offset.y);
+        }
+    } else if let Ok(mut node) = nodes.get_mut(entity) {
+        node.left = Val::Px(drag_state.start.x + offset.x);
+        node.top = Val::Px(drag_state.start.y + offset.y);
+    }
+}
+
+// the inset the layout applies along an axis, in logical pixels; absolutely positioned elements
+// are placed at their inset from their parent's padding box, `layout`, while relatively positioned
+// ones are shifted by it from where they would otherwise be
+fn resolved_inset(val: Val, absolute: bool, layout: f32, parent_size: f32, viewport_size: Vec2) -> f32 {
+    match val {
+        Val::Px(px) => px,
+        _ if absolute => layout,
+        val => val.resolve(parent_size, viewport_size).unwrap_or(0.),
+    }
+}
+
+fn end_drag(
+    mut drag_end: Trigger<Pointer<DragEnd>>,
+    drag_states: Query<&DragState>,
+    drop_targets: Query<(Entity, &DragHovered)>,
+    mut nodes: Query<&mut Node>,
+    mut cursor_stack: ResMut<CursorStack>,
+    mut commands: Commands,
+) {
+    if !matches!(drag_end.button, PointerButton::Primary) {
+        return;
+    }
+    drag_end.propagate(false);
+    let entity = drag_end.target();
+    let Ok(drag_state) = drag_states.get(entity) else {
+        return;
+    };
+    cursor_stack.remove(entity, CursorPriority::DRAG);
+    let position = drag_end.pointer_location.position;
+    for (target, &DragHovered(dragged)) in drop_targets.iter() {
+        if dragged == Some(entity) {
+            for phase in [DropPhase::Drop, DropPhase::Leave] {
+                commands.trigger_targets(
+                    DropTargetPhase {
+                        phase,
+                        dragged: entity,
+                        position,
+                    },
+                    target,
+                );
+            }
+            if let Ok(mut target) = commands.get_entity(target) {
+                target.try_insert(DragHovered(None));
+            }
+        }
+    }
+    if let Some(ghost) = drag_state.ghost {
+        if let Ok(mut ghost) = commands.get_entity(ghost) {
+            ghost.despawn();
+        }
+    } else if let Ok(mut node) = nodes.get_mut(entity) {
+        node.left = drag_state.left;
+        node.top = drag_state.top;
+    }
+    if let Ok(mut entity) = commands.get_entity(entity) {
+        entity.remove::<DragState>();
+        match drag_state.pickable.clone() {
+            Some(pickable) => entity.try_insert(pickable),
+            None => entity.remove::<Pickable>(),
+        };
+        if drag_state.ghost.is_none() {
+            match drag_state.global_z_index {
+                Some(global_z_index) => entity.try_insert(global_z_index),
+                None => entity.remove::<GlobalZIndex>(),
+            };
+        }
+    }
+    commands.trigger_targets(DraggingChange(false), entity);
+}
+
+/// Which [`Draggable`] element is currently dragged over this [`DropTarget`].
+#[derive(Component, Default)]
+struct DragHovered(Option<Entity>);
+
+#[derive(Component)]
+struct DropRejected;
+
+#[derive(Clone, Copy, PartialEq)]
+enum DropPhase {
+    Enter,
+    Over,
+    Leave,
+    Drop,
+}
+
+#[derive(Event, Clone, Copy)]
+struct DropTargetPhase {
+    phase: DropPhase,
+    dragged: Entity,
+    // in logical window pixels
+    position: Vec2,
+}
+
+/// Enables reacting to [`Draggable`] elements being dragged over and dropped onto an
+/// [`Element`](super::element::Element). Handlers only run for [`Draggable`] elements carrying a
+/// payload of the handler's type `T`. When [`DropTarget`]s are nested, only the innermost
+/// hovered one that [`accepts`](DropTarget::accepts) drops receives them.
+pub trait DropTarget: PointerEventAware {
+    /// When a [`Draggable`] element carrying a `T` payload is dragged into this element, run a
+    /// [`System`] which takes [`In`](`System::In`) this element's [`Entity`], the payload, and
+    /// the [`LocalPointer`] in this element's own space. This method can be called repeatedly to
+    /// register many such handlers.
+    fn on_drag_enter_with_system<T: Clone + Send + Sync + 'static, Marker>(
+        self,
+        handler: impl IntoSystem<In<(Entity, (T, LocalPointer))>, (), Marker> + Send + 'static,
+    ) -> Self {
+        self.update_raw_el(|raw_el| observe_drop_phase(raw_el, DropPhase::Enter, handler))
+    }
+
+    /// When a [`Draggable`] element carrying a `T` payload is dragged into this element, run a
+    /// function with the payload.
+    fn on_drag_enter<T: Clone + Send + Sync + 'static>(
+        self,
+        mut handler: impl FnMut(T) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_drag_enter_with_system(move |In((_, (payload, _))): In<(Entity, (T, LocalPointer))>| handler(payload))
+    }
+
+    /// When a [`Draggable`] element carrying a `T` payload is dragged out of this element, or its
+    /// drag ends, run a [`System`] which takes [`In`](`System::In`) this element's [`Entity`], the
+    /// payload, and the [`LocalPointer`] in this element's own space. This method can be called
+    /// repeatedly to register many such handlers.
+    fn on_drag_leave_with_system<T: Clone + Send + Sync + 'static, Marker>(
+        self,
+        handler: impl IntoSystem<In<(Entity, (T, LocalPointer))>, (), Marker> + Send + 'static,
+    ) -> Self {
+        self.update_raw_el(|raw_el| observe_drop_phase(raw_el, DropPhase::Leave, handler))
+    }
+
+    /// When a [`Draggable`] element carrying a `T` payload is dragged out of this element, or its
+    /// drag ends, run a function with the payload.
+    fn on_drag_leave<T: Clone + Send + Sync + 'static>(
+        self,
+        mut handler: impl FnMut(T) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_drag_leave_with_system(move |In((_, (payload, _))): In<(Entity, (T, LocalPointer))>| handler(payload))
+    }
+
+    /// While a [`Draggable`] element carrying a `T` payload is dragged over this element, on
+    /// frames where it moves, run a [`System`] which takes [`In`](`System::In`) this element's
+    /// [`Entity`], the payload, and the [`LocalPointer`] in this element's own space. This method
+    /// can be called repeatedly to register many such handlers.
+    fn on_drag_over_with_system<T: Clone + Send + Sync + 'static, Marker>(
+        self,
+        handler: impl IntoSystem<In<(Entity, (T, LocalPointer))>, (), Marker> + Send + 'static,
+    ) -> Self {
+        self.update_raw_el(|raw_el| observe_drop_phase(raw_el, DropPhase::Over, handler))
+    }
+
+    /// While a [`Draggable`] element carrying a `T` payload is dragged over this element, on
+    /// frames where it moves, run a function with the payload and the [`LocalPointer`] in this
+    /// element's own space.
+    fn on_drag_over<T: Clone + Send + Sync + 'static>(
+        self,
+        mut handler: impl FnMut(T, LocalPointer) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_drag_over_with_system(move |In((_, (payload, local))): In<(Entity, (T, LocalPointer))>| {
+            handler(payload, local)
+        })
+    }
+
+    /// When a [`Draggable`] element carrying a `T` payload is dropped onto this element, run a
+    /// [`System`] which takes [`In`](`System::In`) this element's [`Entity`], the payload, and
+    /// the [`LocalPointer`] in this element's own space. This method can be called repeatedly to
+    /// register many such handlers.
+    fn on_drop_with_system<T: Clone + Send + Sync + 'static, Marker>(
+        self,
+        handler: impl IntoSystem<In<(Entity, (T, LocalPointer))>, (), Marker> + Send + 'static,
+    ) -> Self {
+        self.update_raw_el(|raw_el| observe_drop_phase(raw_el, DropPhase::Drop, handler))
+    }
+
+    /// When a [`Draggable`] element carrying a `T` payload is dropped onto this element, run a
+    /// function with the payload.
+    fn on_drop<T: Clone + Send + Sync + 'static>(self, mut handler: impl FnMut(T) + Send + Sync + 'static) -> Self {
+        self.on_drop_with_system(move |In((_, (payload, _))): In<(Entity, (T, LocalPointer))>| handler(payload))
+    }
+
+    /// Reactively set whether this element accepts drops; while the [`Signal`] outputs `false`,
+    /// dragging over this element is treated as if it were not hovered. Drops are accepted by
+    /// default.
+    fn accepts<S: Signal<Item = bool> + Send + 'static>(mut self, accepts_signal_option: impl Into<Option<S>>) -> Self {
+        if let Some(accepts_signal) = accepts_signal_option.into() {
+            self = self.update_raw_el(|raw_el| {
+                raw_el.component_signal::<DropRejected, _>(
+                    accepts_signal.map(|accepts| (!accepts).then_some(DropRejected)),
+                )
+            });
+        }
+        self
+    }
+}
+
+fn observe_drop_phase<T: Clone + Send + Sync + 'static, Marker>(
+    raw_el: RawHaalkaEl,
+    phase: DropPhase,
+    handler: impl IntoSystem<In<(Entity, (T, LocalPointer))>, (), Marker> + Send + 'static,
+) -> RawHaalkaEl {
+    let system_holder = Arc::new(OnceLock::new());
+    raw_el
+        .insert(Pickable::default())
+        .with_entity(|mut entity| {
+            entity.insert_if_new(DragHovered::default());
+        })
+        .on_spawn(clone!((system_holder) move |world, entity| {
+            let system = register_system(world, handler);
+            let _ = system_holder.set(system);
+            observe(world, entity, move |event: Trigger<DropTargetPhase>, payloads: Query<&DragPayload<T>>, element_space: ElementSpace, mut commands: Commands| {
+                let &DropTargetPhase { phase: event_phase, dragged, position } = event.event();
+                if event_phase == phase
+                    && let Ok(DragPayload(payload)) = payloads.get(dragged)
+                    && let Some(local) = element_space.get(entity, position, Vec2::ZERO, Vec2::ZERO)
+                {
+                    commands.run_system_with(system, (entity, (payload.clone(), local)));
+                }
+            });
+        }))
+        .apply(remove_system_holder_on_remove(system_holder))
+}
+
+// TODO: support multiple pointers
+#[allow(clippy::type_complexity)]
+fn update_drop_target_states(
+    drag_states: Query<(Entity, Ref<DragState>)>,
+    hover_map: Res<HoverMap>,
+    mut drop_targets: Query<(Entity, &mut DragHovered, Has<DropRejected>)>,
+    child_ofs: Query<&ChildOf>,
+    mut commands: Commands,
+) {
+    let within = |entity: Entity, ancestor: Entity| {
+        entity == ancestor || child_ofs.iter_ancestors(entity).any(|e| e == ancestor)
+    };
+    let drag_option = drag_states.iter().next();
+    let mut hovered_targets = vec![];
+    if let Some((dragged, drag_state)) = &drag_option
+        && let Some(hover_set) = hover_map.get(&drag_state.pointer_id)
+    {
+        for (target, _, rejected) in drop_targets.iter() {
+            // hits on the dragged element itself do not count
+            if !rejected
+                && hover_set
+                    .keys()
+                    .any(|&hit| !within(hit, *dragged) && within(hit, target))
+            {
+                hovered_targets.push(target);
+            }
+        }
+    }
+    // only the innermost hovered target receives the drag
+    let innermost = hovered_targets
+        .iter()
+        .copied()
+        .filter(|&target| {
+            !hovered_targets
+                .iter()
+                .any(|&other| other != target && within(other, target))
+        })
+        .collect::<Vec<_>>();
+    for (target, mut drag_hovered, _) in drop_targets.iter_mut() {
+        let hovered_option = drag_option
+            .as_ref()
+            .filter(|_| innermost.contains(&target))
+            .map(|(dragged, _)| *dragged);
+        let position = drag_option
+            .as_ref()
+            .map(|(_, drag_state)| drag_state.position)
+            .unwrap_or_default();
+        if drag_hovered.0 != hovered_option {
+            if let Some(dragged) = drag_hovered.0 {
+                commands.trigger_targets(
+                    DropTargetPhase {
+                        phase: DropPhase::Leave,
+                        dragged,
+                        position,
+                    },
+                    target,
+                );
+            }
+            if let Some(dragged) = hovered_option {
+                commands.trigger_targets(
+                    DropTargetPhase {
+                        phase: DropPhase::Enter,
+                        dragged,
+                        position,
+                    },
+                    target,
+                );
+            }
+            drag_hovered.0 = hovered_option;
+        } else if let Some(dragged) = hovered_option
+            && let Some((_, drag_state)) = &drag_option
+            && drag_state.is_changed()
+        {
+            commands.trigger_targets(
+                DropTargetPhase {
+                    phase: DropPhase::Over,
+                    dragged,
+                    position,
+                },
+                target,
+            );
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+struct ReorderDrag {
+    // index of the dragged item
+    from: usize,
+    // index the dragged item would be moved to if dropped now
+    to: usize,
+    // how far the other items shift to make room for the dragged item, in logical pixels
+    gap: f32,
+}
+
+/// Drag state shared by the items of a single reorderable list.
+#[derive(Clone, Default)]
+struct Reorder {
+    drag: Mutable<Option<ReorderDrag>>,
+    // the items' rects when the drag started, in logical pixels
+    slots: Arc<Mutex<Vec<Rect>>>,
+}
+
+#[derive(Component, Clone)]
+struct ReorderItem(Reorder);
+
+// reorderable lists are laid out along a single axis, so `Axis::Both` is only nominally handled
+fn along(axis: Axis, vec: Vec2) -> f32 {
+    match axis {
+        Axis::Horizontal => vec.x,
+        Axis::Vertical | Axis::Both => vec.y,
+    }
+}
+
+// how far the item at `index` is shifted along the axis, [`None`] for the dragged item itself,
+// whose position is managed by [`Draggable`]
+fn reorder_shift(index: Option<usize>, drag: Option<ReorderDrag>) -> Option<f32> {
+    let Some(ReorderDrag { from, to, gap }) = drag else {
+        return Some(0.);
+    };
+    let index = index?;
+    if index == from {
+        None
+    } else if from < index && index <= to {
+        Some(-gap)
+    } else if to <= index && index < from {
+        Some(gap)
+    } else {
+        Some(0.)
+    }
+}
+
+/// Wrap each item of a [`MutableVec`] in a [`Draggable`] [`El`] which can be dragged along the
+/// `axis` to reorder the [`MutableVec`]; while an item is dragged, the other items shift to leave
+/// a gap where it would be dropped, and dropping it moves it in the [`MutableVec`], producing a
+/// [`VecDiff::Move`](futures_signals::signal_vec::VecDiff::Move).
+pub(crate) fn reorderable_items<T: Clone + Send + Sync + 'static, IOE: IntoOptionElement + 'static>(
+    items: MutableVec<T>,
+    axis: Axis,
+    mut item: impl FnMut(T) -> IOE + Send + 'static,
+) -> impl SignalVec<Item = El<Node>> + Send + 'static {
+    let reorder = Reorder::default();
+    items
+        .signal_vec_cloned()
+        .enumerate()
+        .map(move |(index, value)| {
+            let shift = map_ref! {
+                let index = index.signal(),
+                let drag = reorder.drag.signal() => reorder_shift(*index, *drag)
+            };
+            El::<Node>::new()
+                .update_raw_el(clone!((reorder) move |raw_el| raw_el.insert(ReorderItem(reorder))))
+                .draggable(value.clone())
+                .drag_axis(axis)
+                .drag_bounds(DragBounds::Parent)
+                .on_signal_with_node(shift.dedupe(), move |mut node, shift| {
+                    if let Some(shift) = shift {
+                        match axis {
+                            Axis::Horizontal => node.left = Val::Px(shift),
+                            Axis::Vertical | Axis::Both => node.top = Val::Px(shift),
+                        }
+                    }
+                })
+                .on_drag_start_local_with_system(clone!((reorder) move |In((entity, _)): In<(Entity, LocalPointer)>, element_space: ElementSpace, child_ofs: Query<&ChildOf>, childrens: Query<&Children>, reorder_items: Query<&ReorderItem>| {
+                    let Ok(&ChildOf(parent)) = child_ofs.get(entity) else { return };
+                    let Ok(children) = childrens.get(parent) else { return };
+                    let siblings = children
+                        .iter()
+                        .filter(|&child| {
+                            reorder_items
+                                .get(child)
+                                .is_ok_and(|ReorderItem(other)| Arc::ptr_eq(&other.slots, &reorder.slots))
+                        })
+                        .collect::<Vec<_>>();
+                    let (Some(from), Some(slots)) = (
+                        siblings.iter().position(|&sibling| sibling == entity),
+                        siblings.iter().map(|&sibling| element_space.rect(sibling)).collect::<Option<Vec<_>>>(),
+                    ) else {
+                        return;
+                    };
+                    // include the spacing between items in the gap
+                    let size = along(axis, slots[from].size());
+                    let spacing = match (from.checked_sub(1).and_then(|i| slots.get(i)), slots.get(from + 1)) {
+                        (_, Some(next)) => along(axis, next.min - slots[from].max),
+                        (Some(previous), None) => along(axis, slots[from].min - previous.max),
+                        (None, None) => 0.,
+                    };
+                    *reorder.slots.lock().unwrap() = slots;
+                    reorder.drag.set(Some(ReorderDrag { from, to: from, gap: size + spacing.max(0.) }));
+                }))
+                .on_drag_local(clone!((reorder) move |local| {
+                    let Some(drag) = reorder.drag.get() else { return };
+                    let slots = reorder.slots.lock().unwrap();
+                    let Some(origin) = slots.get(drag.from) else { return };
+                    let center = along(axis, origin.center()) + along(axis, local.distance);
+                    let to = slots
+                        .iter()
+                        .enumerate()
+                        .filter(|&(i, slot)| i != drag.from && along(axis, slot.center()) < center)
+                        .count();
+                    reorder.drag.set_neq(Some(ReorderDrag { to, ..drag }));
+                }))
+                .on_dragging_change(clone!((reorder, items) move |dragging| {
+                    if !dragging
+                        && let Some(ReorderDrag { from, to, .. }) = reorder.drag.replace(None)
+                        && from != to
+                    {
+                        items.lock_mut().move_from_to(from, to);
+                    }
+                }))
+                .child(item(value))
+        })
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        update_drop_target_states.run_if(any_with_component::<DragHovered>),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pointer_event_aware::ignore_tree_picking;
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_picking::{
+        backend::{HitData, PointerHits},
+        hover::{PreviousHoverMap, generate_hovermap},
+        pointer::PointerInput,
+    };
+
+    #[test]
+    fn drag_ghosts_let_drop_targets_be_hovered() {
+        let mut world = World::new();
+        world.init_resource::<HoverMap>();
+        world.init_resource::<PreviousHoverMap>();
+        world.init_resource::<Events<PointerHits>>();
+        world.init_resource::<Events<PointerInput>>();
+        // touch drags resolve drop targets too
+        let pointer_id = PointerId::Touch(0);
+        world.spawn(pointer_id);
+
+        let target = world.spawn(DragHovered::default()).id();
+        let ghost = world.spawn(PickingIgnoredTree).id();
+        let content = world.spawn(ChildOf(ghost)).id();
+        let dragged = world
+            .spawn(DragState {
+                origin: Rect::default(),
+                range: None,
+                offset: Vec2::ZERO,
+                position: Vec2::ZERO,
+                pointer_id,
+                start: Vec2::ZERO,
+                left: Val::Auto,
+                top: Val::Auto,
+                pickable: None,
+                global_z_index: None,
+                ghost: Some(ghost),
+            })
+            .id();
+        world.run_system_once(ignore_tree_picking).unwrap();
+
+        let camera = world.spawn_empty().id();
+        let hit = |depth| HitData::new(camera, depth, None, None);
+        world.send_event(PointerHits::new(
+            pointer_id,
+            vec![(content, hit(0.)), (ghost, hit(1.)), (target, hit(2.))],
+            0.,
+        ));
+        world.run_system_once(generate_hovermap).unwrap();
+        world.run_system_once(update_drop_target_states).unwrap();
+        assert_eq!(world.get::<DragHovered>(target).unwrap().0, Some(dragged));
+    }
+
+    #[test]
+    fn drags_start_from_the_resolved_inset() {
+        let viewport_size = Vec2::new(1000., 500.);
+        assert_eq!(resolved_inset(Val::Px(10.), false, 50., 200., viewport_size), 10.);
+        assert_eq!(resolved_inset(Val::Auto, false, 50., 200., viewport_size), 0.);
+        assert_eq!(resolved_inset(Val::Percent(25.), false, 50., 200., viewport_size), 50.);
+        assert_eq!(resolved_inset(Val::Vw(10.), false, 50., 200., viewport_size), 100.);
+        assert_eq!(resolved_inset(Val::Percent(25.), true, 40., 200., viewport_size), 40.);
+        assert_eq!(resolved_inset(Val::Auto, true, 40., 200., viewport_size), 40.);
+    }
+}
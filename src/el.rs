@@ -6,12 +6,14 @@ use futures_signals::signal::{Signal, SignalExt};
 use super::{
     align::{AddRemove, AlignHolder, Alignable, Aligner, Alignment, ChildAlignable},
     column::Column,
+    draggable::{Draggable, DropTarget},
     element::{IntoOptionElement, Nameable, UiRootable},
     global_event_aware::GlobalEventAware,
     mouse_wheel_scrollable::MouseWheelScrollable,
     pointer_event_aware::{CursorOnHoverable, PointerEventAware},
     raw::{RawElWrapper, RawHaalkaEl},
-    viewport_mutable::ViewportMutable,
+    tooltip::Tooltipable,
+    viewport_mutable::{ScrollIntoViewable, Stickable, ViewportMutable},
 };
 
 // TODO: add the extra flag machinery that MoonZoon has to ensure that El's have exactly one child
@@ -66,11 +68,16 @@ impl<NodeType> RawElWrapper for El<NodeType> {
 }
 
 impl<NodeType: Bundle> CursorOnHoverable for El<NodeType> {}
+impl<NodeType: Bundle> Draggable for El<NodeType> {}
+impl<NodeType: Bundle> DropTarget for El<NodeType> {}
 impl<NodeType: Bundle> GlobalEventAware for El<NodeType> {}
 impl<NodeType: Bundle> Nameable for El<NodeType> {}
 impl<NodeType: Bundle> PointerEventAware for El<NodeType> {}
 impl<NodeType: Bundle> MouseWheelScrollable for El<NodeType> {}
 impl<NodeType: Bundle> UiRootable for El<NodeType> {}
+impl<NodeType: Bundle> Tooltipable for El<NodeType> {}
+impl<NodeType: Bundle> ScrollIntoViewable for El<NodeType> {}
+impl<NodeType: Bundle> Stickable for El<NodeType> {}
 impl<NodeType: Bundle> ViewportMutable for El<NodeType> {}
 
 impl<NodeType: Bundle> El<NodeType> {
@@ -11,13 +11,15 @@ use futures_signals::{
 
 use super::{
     align::{AddRemove, AlignHolder, Alignable, Aligner, Alignment, ChildAlignable},
+    draggable::{Draggable, DropTarget},
     element::{IntoOptionElement, Nameable, UiRootable},
     global_event_aware::GlobalEventAware,
     mouse_wheel_scrollable::MouseWheelScrollable,
     pointer_event_aware::{CursorOnHoverable, PointerEventAware},
     raw::{RawElWrapper, RawHaalkaEl},
     stack::Stack,
-    viewport_mutable::ViewportMutable,
+    tooltip::Tooltipable,
+    viewport_mutable::{ScrollIntoViewable, Stickable, ViewportMutable},
 };
 
 /// [`Element`](super::element::Element) with children aligned in a grid using a simple [`.row_wrap_cell_width`](Grid::row_wrap_cell_width) grid layout model. Port of [MoonZoon](https://github.com/MoonZoon/MoonZoon)'s [`Grid`](https://github.com/MoonZoon/MoonZoon/blob/main/crates/zoon/src/element/grid.rs).
@@ -74,11 +76,16 @@ impl<NodeType: Bundle> RawElWrapper for Grid<NodeType> {
 }
 
 impl<NodeType: Bundle> CursorOnHoverable for Grid<NodeType> {}
+impl<NodeType: Bundle> Draggable for Grid<NodeType> {}
+impl<NodeType: Bundle> DropTarget for Grid<NodeType> {}
 impl<NodeType: Bundle> GlobalEventAware for Grid<NodeType> {}
 impl<NodeType: Bundle> Nameable for Grid<NodeType> {}
 impl<NodeType: Bundle> PointerEventAware for Grid<NodeType> {}
 impl<NodeType: Bundle> MouseWheelScrollable for Grid<NodeType> {}
 impl<NodeType: Bundle> UiRootable for Grid<NodeType> {}
+impl<NodeType: Bundle> Tooltipable for Grid<NodeType> {}
+impl<NodeType: Bundle> ScrollIntoViewable for Grid<NodeType> {}
+impl<NodeType: Bundle> Stickable for Grid<NodeType> {}
 impl<NodeType: Bundle> ViewportMutable for Grid<NodeType> {}
 
 /// Must substract this from the total row width of a [`Grid`] due to [float precision shenanigans](https://github.com/bevyengine/bevy/issues/12152). See an example usage in the [snake example](https://github.com/databasedav/haalka/blob/e12350c55d7aace07bc27787989c79d5a4e064e5/examples/snake.rs#L112).
@@ -17,6 +17,7 @@ cfg_if::cfg_if! {
     if #[cfg(feature = "ui")] {
         pub mod align;
         mod column;
+        pub mod draggable;
         mod el;
         pub mod element;
         pub mod grid;
@@ -24,11 +25,18 @@ cfg_if::cfg_if! {
         pub mod global_event_aware;
         mod row;
         pub mod mouse_wheel_scrollable;
+        pub mod scrollbar;
         mod stack;
+        pub mod tooltip;
         pub mod viewport_mutable;
+        pub mod virtualized;
 
         cfg_if::cfg_if! {
             if #[cfg(feature = "text_input")] {
+                pub mod clipboard;
+                pub mod combo_box;
+                pub mod number_input;
+                pub mod text_area;
                 pub mod text_input;
             }
         }
@@ -51,12 +59,16 @@ impl Plugin for HaalkaPlugin {
         {
             app.add_plugins((
                 pointer_event_aware::plugin,
+                draggable::plugin,
+                tooltip::plugin,
                 mouse_wheel_scrollable::plugin,
+                scrollbar::plugin,
                 viewport_mutable::plugin,
+                virtualized::plugin,
             ));
         }
         #[cfg(feature = "text_input")]
-        app.add_plugins(text_input::plugin);
+        app.add_plugins((text_input::plugin, text_area::plugin));
 
         app.add_systems(PreStartup, init_async_world);
     }
@@ -80,17 +92,25 @@ pub mod prelude {
             pub use crate::{
                 align::{Align, Alignable},
                 column::Column,
+                draggable::{DragBounds, Draggable, DropTarget},
                 el::El,
                 element::{Element, ElementWrapper, Nameable, TypeEraseable, UiRoot, UiRootable},
                 global_event_aware::GlobalEventAware,
                 grid::Grid,
                 mouse_wheel_scrollable::{
-                    BasicScrollHandler, MouseWheelScrollable, OnHoverMouseWheelScrollable, ScrollDirection,
+                    BasicScrollHandler, ElasticOverscroll, MouseWheelScrollable, OnHoverMouseWheelScrollable,
+                    ScrollDirection, ScrollMode, ScrollSnap, ScrollSwapKeys,
                 },
-                pointer_event_aware::{SetCursor, CursorOnHoverDisabled, CursorOnHoverable, PointerEventAware, Enter, Leave},
+                pointer_event_aware::{SetCursor, CursorOnHoverDisabled, CursorOnHoverable, CursorPriority, CursorStack, PointerEventAware, Enter, Leave, LocalPointer},
                 row::Row,
+                scrollbar::Scrollbar,
                 stack::Stack,
-                viewport_mutable::{Axis, ViewportMutable},
+                tooltip::{TooltipPlacement, TooltipSettings, Tooltipable},
+                viewport_mutable::{
+                    Axis, AxisScrollMetrics, ScrollAlignment, ScrollIntoViewOptions, ScrollIntoViewable, ScrollMetrics,
+                    Stickable, StickyEdge, ViewportCommands, ViewportMutable,
+                },
+                virtualized::{ItemSize, VirtualColumn, VirtualGrid},
             };
 
             pub use bevy_window::SystemCursorIcon;
@@ -99,7 +119,13 @@ pub mod prelude {
             cfg_if::cfg_if! {
                 if #[cfg(feature = "text_input")] {
                     #[doc(inline)]
-                    pub use super::text_input::TextInput;
+                    pub use super::{
+                        clipboard::{Clipboard, InMemoryClipboard, SystemClipboard, TextInputClipboard},
+                        combo_box::{ComboBox, ComboBoxMatch},
+                        number_input::{NumberInput, NumberInputValue},
+                        text_area::TextArea,
+                        text_input::{FloatingLabel, ObscuredSettings, TextInput, TextInputCommands, TextInputPreset, TextSignalHistory},
+                    };
                     pub use bevy_ui_text_input;
                 }
             }
@@ -9,15 +9,23 @@ use super::{
 };
 use apply::Apply;
 use bevy_app::prelude::*;
-use bevy_ecs::prelude::*;
+use bevy_ecs::{prelude::*, system::SystemParam};
 use bevy_input::{mouse::*, prelude::*};
-use bevy_ui::prelude::*;
+use bevy_math::{
+    curve::{Curve, EaseFunction},
+    prelude::*,
+};
+use bevy_picking::{hover::HoverMap, pointer::PointerId, prelude::*};
+use bevy_time::prelude::*;
+use bevy_transform::{TransformSystem, prelude::*};
+use bevy_ui::{UiSystem, prelude::*};
 use bevy_utils::prelude::*;
 use futures_signals::signal::{BoxSignal, Mutable, Signal, SignalExt, always};
 use haalka_futures_signals_ext::{SignalExtBool, SignalExtExt};
 use std::{
     convert::Into,
     sync::{Arc, OnceLock},
+    time::Duration,
 };
 
 /// Marker [`Component`] that disables an element's viewport from reacting to mouse wheel events.
@@ -27,6 +35,44 @@ pub struct ScrollDisabled;
 #[derive(Component)]
 struct ScrollEnabled;
 
+/// Marker [`Component`] for elements which only receive [`MouseWheel`] events while hovered; these
+/// are routed to the innermost such element under the pointer rather than broadcast.
+#[derive(Component)]
+struct ScrollOnHover;
+
+/// Marker [`Component`] that prevents [`MouseWheel`] events from chaining from an element to its
+/// scrollable ancestors when its viewport is already scrolled to its limit, like CSS's
+/// `overscroll-behavior: contain`.
+#[derive(Component, Default)]
+pub struct ScrollChainingDisabled;
+
+/// [`Component`] setting the keys which, while any of them are held, swap the horizontal and
+/// vertical components of mouse wheel events for a [`BasicScrollHandler`] with
+/// [`ScrollDirection::Both`]; elements without one swap with either shift key.
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct ScrollSwapKeys(pub Vec<KeyCode>);
+
+/// [`Component`] that snaps an element's viewport to its children's boundaries, like CSS's
+/// `scroll-snap-align`. Each mouse wheel step advances the viewport to the next child in the step's
+/// direction, and releasing a [`MouseWheelScrollable::drag_to_scroll`] drag, or the glide that
+/// follows it, settles the viewport on the nearest child.
+#[derive(Component, Clone, Copy, Default, Debug, PartialEq)]
+#[require(SnapLayoutOffset)]
+pub enum ScrollSnap {
+    /// Align the start of a child with the start of the viewport.
+    #[default]
+    Start,
+    /// Align the center of a child with the center of the viewport.
+    Center,
+    /// Align the end of a child with the end of the viewport.
+    End,
+}
+
+// the scroll offset, in physical pixels, that the last layout positioned a [`ScrollSnap`] viewport's
+// children with, which its [`ScrollPosition`] may have since moved past
+#[derive(Component, Default, PartialEq)]
+struct SnapLayoutOffset(Vec2);
+
 /// Enables an element's viewport to be modified and react to mouse wheel events.
 pub trait MouseWheelScrollable: ViewportMutable {
     /// When this element receives a [`MouseWheel`] event, if it does not have a `Disabled`
@@ -112,6 +158,121 @@ pub trait MouseWheelScrollable: ViewportMutable {
         self.update_raw_el(|raw_el| raw_el.component_signal::<ScrollDisabled, _>(blocked.map_true(default)))
             .on_scroll_disableable::<ScrollDisabled>(handler)
     }
+
+    /// Scroll this element's viewport by dragging it with the primary pointer button. With
+    /// [`ScrollMode::Kinetic`], the viewport keeps gliding after the drag is released; other modes
+    /// follow the pointer directly.
+    fn drag_to_scroll(self, mode: ScrollMode) -> Self {
+        // how a [`ScrollSnap`] viewport settles on a child after the drag
+        let snap_mode = match mode {
+            ScrollMode::Smooth { .. } => mode,
+            _ => ScrollMode::SMOOTH,
+        };
+        let mode = match mode {
+            ScrollMode::Kinetic { .. } => mode,
+            _ => ScrollMode::Instant,
+        };
+        self.update_raw_el(|raw_el| {
+            raw_el
+                .insert(Pickable::default())
+                .observe(
+                    move |mut drag: Trigger<Pointer<Drag>>, ui_scale: Res<UiScale>, mut commands: Commands| {
+                        if !matches!(drag.button, PointerButton::Primary) {
+                            return;
+                        }
+                        drag.propagate(false);
+                        commands.trigger_targets(
+                            ScrollBy {
+                                delta: -drag.delta / ui_scale.0,
+                                mode,
+                                held: true,
+                            },
+                            drag.target(),
+                        );
+                    },
+                )
+                .observe(
+                    move |mut drag_end: Trigger<Pointer<DragEnd>>,
+                          time: Res<Time>,
+                          mut momentums: Query<&mut ScrollMomentum>,
+                          mut overscrolls: Query<&mut Overscroll>,
+                          mut commands: Commands| {
+                        if !matches!(drag_end.button, PointerButton::Primary) {
+                            return;
+                        }
+                        drag_end.propagate(false);
+                        let entity = drag_end.target();
+                        if let Ok(mut overscroll) = overscrolls.get_mut(entity) {
+                            overscroll.held = false;
+                        }
+                        if let Ok(mut momentum) = momentums.get_mut(entity) {
+                            momentum.held = false;
+                            // the pointer was held still before release
+                            if time.elapsed().saturating_sub(momentum.last_input) >= GESTURE_TIMEOUT {
+                                momentum.velocity = Vec2::ZERO;
+                            }
+                            // snapping waits for the glide to end
+                        } else {
+                            commands.trigger_targets(SnapScroll { mode: snap_mode }, entity);
+                        }
+                    },
+                )
+        })
+    }
+
+    /// Let scrolling input past the limits of this element's viewport stretch its contents, see
+    /// [`ElasticOverscroll`]. Passing [`None`] disables the effect.
+    fn elastic_overscroll(self, elastic_option: impl Into<Option<ElasticOverscroll>>) -> Self {
+        let elastic_option = elastic_option.into();
+        self.update_raw_el(|raw_el| {
+            raw_el.with_entity(move |mut entity| {
+                if let Some(elastic) = elastic_option {
+                    entity.insert(elastic);
+                } else {
+                    entity.remove::<ElasticOverscroll>();
+                }
+            })
+        })
+    }
+
+    /// Snap this element's viewport to its children's boundaries, see [`ScrollSnap`]. Passing
+    /// [`None`] disables snapping.
+    fn scroll_snap(self, snap_option: impl Into<Option<ScrollSnap>>) -> Self {
+        let snap_option = snap_option.into();
+        self.update_raw_el(|raw_el| {
+            raw_el.with_entity(move |mut entity| {
+                if let Some(snap) = snap_option {
+                    entity.insert(snap);
+                } else {
+                    entity.remove::<ScrollSnap>();
+                }
+            })
+        })
+    }
+
+    /// Set the keys which, while any of them are held, swap the horizontal and vertical components
+    /// of the mouse wheel events this element's [`BasicScrollHandler`] receives, so a mouse wheel
+    /// that only scrolls vertically can scroll horizontally, see [`ScrollSwapKeys`]; defaults to
+    /// either shift key. Pass no keys to disable swapping.
+    fn scroll_swap_keys(self, keys: impl IntoIterator<Item = KeyCode>) -> Self {
+        let swap_keys = ScrollSwapKeys(keys.into_iter().collect());
+        self.update_raw_el(|raw_el| raw_el.insert(swap_keys))
+    }
+
+    /// Set whether [`MouseWheel`] events this element receives while hovered chain to its
+    /// scrollable ancestors once its viewport is scrolled to its limit in the event's direction.
+    /// Chaining is enabled by default.
+    fn scroll_chaining(self, chaining: bool) -> Self {
+        self.update_raw_el(|raw_el| {
+            raw_el.with_entity(move |mut entity| {
+                if chaining {
+                    entity.remove::<ScrollChainingDisabled>();
+                } else {
+                    entity.insert(ScrollChainingDisabled);
+                }
+            })
+        })
+    }
 }
 
 /// Convenience trait for enabling scrollability when hovering over an element.
@@ -120,6 +281,11 @@ pub trait OnHoverMouseWheelScrollable: MouseWheelScrollable + PointerEventAware
     /// [`ScrollDisabled`] component, run a [`System`] which takes [`In`](`System::In`) this
     /// element's [`Entity`] and the [`MouseWheel`]. This method can be called repeatedly to
     /// register many such handlers.
+    ///
+    /// When such elements are nested, only the innermost one under the pointer receives the event,
+    /// unless its viewport is already scrolled to its limit in the event's direction, in which case
+    /// the event chains to the next such ancestor, see
+    /// [`.scroll_chaining`](MouseWheelScrollable::scroll_chaining).
     fn on_scroll_with_system_on_hover<Marker>(
         self,
         handler: impl IntoSystem<In<(Entity, MouseWheel)>, (), Marker> + Send + 'static,
@@ -134,7 +300,7 @@ pub trait OnHoverMouseWheelScrollable: MouseWheelScrollable + PointerEventAware
             }
         })
         .on_scroll_with_system_disableable::<ScrollDisabled, _>(handler)
-        .update_raw_el(|raw_el| raw_el.insert(ScrollDisabled))
+        .update_raw_el(|raw_el| raw_el.insert((ScrollDisabled, ScrollOnHover)))
     }
 
     /// When this element receives a [`MouseWheel`] event while it is hovered, run a function with
@@ -146,14 +312,94 @@ pub trait OnHoverMouseWheelScrollable: MouseWheelScrollable + PointerEventAware
 
 impl<T: PointerEventAware + MouseWheelScrollable> OnHoverMouseWheelScrollable for T {}
 
+// the largest [`ScrollPosition`] offsets of a node, in logical pixels
+pub(crate) fn max_scroll_offset(computed_node: &ComputedNode) -> Vec2 {
+    (computed_node.content_size() - computed_node.size()).max(Vec2::ZERO) * computed_node.inverse_scale_factor()
+}
+
+// whether `node` is scrolled as far as it can be in the direction `mouse_wheel` asks for; elements
+// which do not scroll their own contents are never at their limit
+fn at_scroll_limit(
+    (node, computed_node, scroll_position): (&Node, &ComputedNode, &ScrollPosition),
+    mouse_wheel: &MouseWheel,
+    swapped: bool,
+) -> bool {
+    let scroll_x = node.overflow.x == OverflowAxis::Scroll;
+    let scroll_y = node.overflow.y == OverflowAxis::Scroll;
+    if !scroll_x && !scroll_y {
+        return false;
+    }
+    // positive wheel deltas scroll towards the start
+    let mut delta = -Vec2::new(mouse_wheel.x, mouse_wheel.y);
+    if !scroll_y {
+        delta = Vec2::new(delta.x + delta.y, 0.);
+    } else if swapped && scroll_x {
+        delta = delta.yx();
+    }
+    let max = max_scroll_offset(computed_node);
+    let can_scroll = |scrolls: bool, delta: f32, offset: f32, max: f32| {
+        scrolls && (delta < 0. && offset > 0. || delta > 0. && offset < max)
+    };
+    !(can_scroll(scroll_x, delta.x, scroll_position.offset_x, max.x)
+        || can_scroll(scroll_y, delta.y, scroll_position.offset_y, max.y))
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn scroll_system(
     mut mouse_wheel_events: EventReader<MouseWheel>,
-    scroll_listeners: Query<Entity, With<ScrollEnabled>>,
+    global_listeners: Query<Entity, (With<ScrollEnabled>, Without<ScrollOnHover>)>,
+    hover_listeners: Query<(), (With<ScrollEnabled>, With<ScrollOnHover>)>,
+    hover_map: Res<HoverMap>,
+    child_ofs: Query<&ChildOf>,
+    viewports: Query<(&Node, &ComputedNode, &ScrollPosition)>,
+    chaining_disabled: Query<(), With<ScrollChainingDisabled>>,
+    swap_keys: Query<&ScrollSwapKeys>,
+    keys: Res<ButtonInput<KeyCode>>,
     mut commands: Commands,
 ) {
-    let listeners = scroll_listeners.iter().collect::<Vec<_>>();
+    let listeners = global_listeners.iter().collect::<Vec<_>>();
+    // hovered listeners along the picking hit paths, innermost first
+    let mut chain = vec![];
+    if let Some(hover_set) = hover_map.get(&PointerId::Mouse) {
+        let nearest_listener = |hit: Entity| {
+            std::iter::once(hit)
+                .chain(child_ofs.iter_ancestors(hit))
+                .find(|&entity| hover_listeners.contains(entity))
+        };
+        // the listener nearest to the topmost hit which has one, with ties broken by entity so
+        // siblings hit at the same depth are chosen consistently
+        let innermost = hover_set
+            .iter()
+            .filter_map(|(&hit, hit_data)| Some((hit_data.depth, hit, nearest_listener(hit)?)))
+            .min_by(|(depth, hit, _), (other_depth, other_hit, _)| {
+                depth.total_cmp(other_depth).then(hit.cmp(other_hit))
+            })
+            .map(|(_, _, listener)| listener);
+        if let Some(innermost) = innermost {
+            chain = std::iter::once(innermost)
+                .chain(child_ofs.iter_ancestors(innermost))
+                .filter(|&entity| hover_listeners.contains(entity))
+                .collect();
+        }
+    }
+    let swapped = |entity: Entity| swap_keys_pressed(&swap_keys, &keys, entity);
     for &event in mouse_wheel_events.read() {
         commands.trigger_targets(event, listeners.clone());
+        // the first element in the chain that can still scroll, or that does not chain, receives
+        // the event; if none can scroll, the innermost one does
+        let target = chain
+            .iter()
+            .copied()
+            .find(|&entity| {
+                chaining_disabled.contains(entity)
+                    || !viewports
+                        .get(entity)
+                        .is_ok_and(|viewport| at_scroll_limit(viewport, &event, swapped(entity)))
+            })
+            .or(chain.first().copied());
+        if let Some(target) = target {
+            commands.trigger_targets(event, target);
+        }
     }
 }
 
@@ -171,10 +417,19 @@ pub enum ScrollDirection {
 pub struct BasicScrollHandler {
     direction: Option<BoxSignal<'static, ScrollDirection>>,
     magnitude: Option<BoxSignal<'static, f32>>,
+    mode: ScrollMode,
 }
 
 const DEFAULT_SCROLL_DIRECTION: ScrollDirection = ScrollDirection::Vertical;
 const DEFAULT_SCROLL_MAGNITUDE: f32 = 10.;
+const DEFAULT_SWAP_KEYS: [KeyCode; 2] = [KeyCode::ShiftLeft, KeyCode::ShiftRight];
+
+fn swap_keys_pressed(swap_keys: &Query<&ScrollSwapKeys>, keys: &ButtonInput<KeyCode>, entity: Entity) -> bool {
+    match swap_keys.get(entity) {
+        Ok(ScrollSwapKeys(swap_keys)) => keys.any_pressed(swap_keys.iter().copied()),
+        Err(_) => keys.any_pressed(DEFAULT_SWAP_KEYS),
+    }
+}
 
 /// Normalizes the scroll amount based on the scroll unit and the specified magnitude.
 pub fn scroll_normalizer(unit: MouseScrollUnit, scroll: f32, magnitude: f32) -> f32 {
@@ -230,6 +485,14 @@ impl BasicScrollHandler {
         self
     }
 
+    /// Set how the viewport moves in response to mouse wheel events, see [`ScrollMode`].
+    pub fn mode(mut self, mode_option: impl Into<Option<ScrollMode>>) -> Self {
+        if let Some(mode) = mode_option.into() {
+            self.mode = mode;
+        }
+        self
+    }
+
     // TODO: is there a better return type for this ?
     /// Convert this [`BasicScrollHandler`] into a function that can be passed as a handler to
     /// `on_scroll_...` methods.
@@ -237,7 +500,7 @@ impl BasicScrollHandler {
     pub fn into_system(
         self,
     ) -> Box<
-        dyn FnMut(In<(Entity, MouseWheel)>, Res<ButtonInput<KeyCode>>, Query<&mut ScrollPosition>)
+        dyn FnMut(In<(Entity, MouseWheel)>, Query<&ScrollSwapKeys>, Res<ButtonInput<KeyCode>>, Commands)
             + Send
             + Sync
             + 'static,
@@ -245,6 +508,7 @@ impl BasicScrollHandler {
         let BasicScrollHandler {
             direction: direction_signal_option,
             magnitude: magnitude_signal_option,
+            mode,
         } = self;
         let direction = Mutable::new(DEFAULT_SCROLL_DIRECTION);
         let magnitude = Mutable::new(DEFAULT_SCROLL_MAGNITUDE);
@@ -265,28 +529,770 @@ impl BasicScrollHandler {
                 .detach()
         }
         let f = move |In((entity, mouse_wheel)): In<(Entity, MouseWheel)>,
+                      swap_keys: Query<&ScrollSwapKeys>,
                       keys: Res<ButtonInput<KeyCode>>,
-                      mut scroll_positions: Query<&mut ScrollPosition>| {
-            let dy = scroll_normalizer(mouse_wheel.unit, mouse_wheel.y, magnitude.get());
-            let direction = direction.get();
-            if let Ok(mut scroll_position) = scroll_positions.get_mut(entity) {
-                if matches!(direction, ScrollDirection::Vertical)
-                    || matches!(direction, ScrollDirection::Both)
-                        && !(keys.pressed(KeyCode::ShiftLeft) || keys.pressed(KeyCode::ShiftRight))
-                {
-                    scroll_position.offset_y -= dy;
-                } else if matches!(direction, ScrollDirection::Horizontal)
-                    || matches!(direction, ScrollDirection::Both)
-                        && (keys.pressed(KeyCode::ShiftLeft) || keys.pressed(KeyCode::ShiftRight))
-                {
-                    scroll_position.offset_x -= dy;
+                      mut commands: Commands| {
+            let magnitude = magnitude.get();
+            // positive wheel deltas scroll towards the start
+            let mut delta = -Vec2::new(
+                scroll_normalizer(mouse_wheel.unit, mouse_wheel.x, magnitude),
+                scroll_normalizer(mouse_wheel.unit, mouse_wheel.y, magnitude),
+            );
+            let delta = match direction.get() {
+                ScrollDirection::Vertical => Vec2::new(0., delta.y),
+                // vertical mouse wheels scroll horizontal viewports too
+                ScrollDirection::Horizontal => Vec2::new(delta.x + delta.y, 0.),
+                ScrollDirection::Both => {
+                    if swap_keys_pressed(&swap_keys, &keys, entity) {
+                        delta = delta.yx();
+                    }
+                    delta
                 }
-            }
+            };
+            commands.trigger_targets(
+                ScrollBy {
+                    delta,
+                    mode,
+                    held: false,
+                },
+                entity,
+            );
         };
         Box::new(f)
     }
 }
 
+/// How a viewport moves in response to scrolling input.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub enum ScrollMode {
+    /// Jump to the new offset immediately.
+    #[default]
+    Instant,
+    /// Animate toward the new offset over `duration`, following `easing`; scrolling again during
+    /// the animation extends its target.
+    Smooth {
+        #[allow(missing_docs)]
+        duration: Duration,
+        #[allow(missing_docs)]
+        easing: EaseFunction,
+    },
+    /// Move with the input immediately, and once a continuous gesture, like a trackpad swipe or a
+    /// drag, ends, keep gliding with its velocity, losing `friction` times the velocity per second.
+    Kinetic {
+        #[allow(missing_docs)]
+        friction: f32,
+    },
+}
+
+impl ScrollMode {
+    /// [`ScrollMode::Smooth`] with a short ease out.
+    pub const SMOOTH: Self = Self::Smooth {
+        duration: Duration::from_millis(150),
+        easing: EaseFunction::QuadraticOut,
+    };
+
+    /// [`ScrollMode::Kinetic`] with moderate friction.
+    pub const KINETIC: Self = Self::Kinetic { friction: 4. };
+}
+
+// how long after the last input a kinetic gesture is considered over
+const GESTURE_TIMEOUT: Duration = Duration::from_millis(60);
+// speed, in logical pixels per second, below which kinetic scrolling stops
+const MIN_KINETIC_SPEED: f32 = 10.;
+
+/// Scrolls the target viewport by `delta` logical pixels according to `mode`.
+#[derive(Event, Clone, Copy)]
+pub(crate) struct ScrollBy {
+    pub(crate) delta: Vec2,
+    pub(crate) mode: ScrollMode,
+    // whether the gesture producing this input is ongoing regardless of timing, e.g. a held drag
+    pub(crate) held: bool,
+}
+
+/// Settles the target [`ScrollSnap`] viewport on its nearest snap point according to `mode`.
+#[derive(Event, Clone, Copy)]
+struct SnapScroll {
+    mode: ScrollMode,
+}
+
+// snap points closer than this, in logical pixels, are considered reached
+const SNAP_EPSILON: f32 = 0.5;
+
+#[derive(Component)]
+struct SmoothScroll {
+    from: Vec2,
+    to: Vec2,
+    elapsed: Duration,
+    duration: Duration,
+    easing: EaseFunction,
+}
+
+#[derive(Component)]
+struct ScrollMomentum {
+    // in logical pixels per second
+    velocity: Vec2,
+    friction: f32,
+    last_input: Duration,
+    held: bool,
+}
+
+fn scroll_position_vec(scroll_position: &ScrollPosition) -> Vec2 {
+    Vec2::new(scroll_position.offset_x, scroll_position.offset_y)
+}
+
+fn set_scroll_position(scroll_position: &mut ScrollPosition, position: Vec2) {
+    scroll_position.offset_x = position.x;
+    scroll_position.offset_y = position.y;
+}
+
+/// The [`ScrollPosition`] offsets, per axis, at which each child of a [`ScrollSnap`] viewport is
+/// aligned with it.
+#[derive(SystemParam)]
+struct SnapPoints<'w, 's> {
+    viewports: Query<
+        'w,
+        's,
+        (
+            &'static ScrollSnap,
+            &'static SnapLayoutOffset,
+            &'static ComputedNode,
+            &'static GlobalTransform,
+            Option<&'static Children>,
+        ),
+    >,
+    nodes: Query<'w, 's, (&'static ComputedNode, &'static GlobalTransform)>,
+}
+
+impl SnapPoints<'_, '_> {
+    fn get(&self, entity: Entity) -> Option<[Vec<f32>; 2]> {
+        let (&snap, &SnapLayoutOffset(layout_offset), computed_node, global_transform, children_option) =
+            self.viewports.get(entity).ok()?;
+        // in physical pixels, like `ComputedNode` and `GlobalTransform`
+        let rect = |computed_node: &ComputedNode, global_transform: &GlobalTransform| {
+            Rect::from_center_size(global_transform.translation().truncate(), computed_node.size())
+        };
+        let viewport = rect(computed_node, global_transform);
+        let inset = computed_node.content_inset();
+        let viewport = Rect::new(
+            viewport.min.x + inset.left,
+            viewport.min.y + inset.top,
+            viewport.max.x - inset.right,
+            viewport.max.y - inset.bottom,
+        );
+        let inverse_scale_factor = computed_node.inverse_scale_factor();
+        let max = max_scroll_offset(computed_node);
+        let mut points = [vec![], vec![]];
+        for child in children_option.into_iter().flat_map(|children| children.iter()) {
+            let Ok((child_node, child_transform)) = self.nodes.get(child) else {
+                continue;
+            };
+            if child_node.is_empty() {
+                continue;
+            }
+            let child = rect(child_node, child_transform);
+            // relative to the viewport's content rather than to where it was scrolled to
+            let offset = layout_offset
+                + match snap {
+                    ScrollSnap::Start => child.min - viewport.min,
+                    ScrollSnap::Center => child.center() - viewport.center(),
+                    ScrollSnap::End => child.max - viewport.max,
+                };
+            let point = (offset * inverse_scale_factor).clamp(Vec2::ZERO, max);
+            points[0].push(point.x);
+            points[1].push(point.y);
+        }
+        Some(points)
+    }
+}
+
+// mirrors how `bevy_ui`'s layout clamps and rounds the [`ScrollPosition`] it offsets children by
+fn record_snap_layout_offsets(
+    mut viewports: Query<(&Node, &ComputedNode, &ScrollPosition, &mut SnapLayoutOffset), With<ScrollSnap>>,
+) {
+    for (node, computed_node, scroll_position, mut layout_offset) in viewports.iter_mut() {
+        let position = Vec2::new(
+            if node.overflow.x == OverflowAxis::Scroll {
+                scroll_position.offset_x
+            } else {
+                0.
+            },
+            if node.overflow.y == OverflowAxis::Scroll {
+                scroll_position.offset_y
+            } else {
+                0.
+            },
+        );
+        let position = position.clamp(Vec2::ZERO, max_scroll_offset(computed_node));
+        layout_offset.set_if_neq(SnapLayoutOffset(
+            (position / computed_node.inverse_scale_factor()).round(),
+        ));
+    }
+}
+
+fn nearest(points: &[f32], position: f32) -> Option<f32> {
+    points
+        .iter()
+        .copied()
+        .min_by(|a, b| (a - position).abs().total_cmp(&(b - position).abs()))
+}
+
+// snap a step from `base` to `target` along one axis to the snap point nearest `target`, advancing
+// at least one snap point in the step's direction so small steps are not undone
+fn snap_step(points: &[f32], base: f32, target: f32) -> f32 {
+    let direction = (target - base).signum();
+    if target == base {
+        return target;
+    }
+    let Some(nearest_point) = nearest(points, target) else {
+        return target;
+    };
+    if (nearest_point - base) * direction > SNAP_EPSILON {
+        return nearest_point;
+    }
+    points
+        .iter()
+        .copied()
+        .filter(|point| (point - base) * direction > SNAP_EPSILON)
+        .min_by(|a, b| (a - base).abs().total_cmp(&(b - base).abs()))
+        .unwrap_or(nearest_point)
+}
+
+// fraction of input beyond a viewport's limits which stretches an [`ElasticOverscroll`] viewport
+const OVERSCROLL_RESISTANCE: f32 = 0.5;
+// overscroll, in logical pixels, below which an [`ElasticOverscroll`] viewport is considered settled
+const MIN_OVERSCROLL: f32 = 0.5;
+
+/// [`Component`] that lets scrolling input past the limits of an element's viewport stretch its
+/// contents, which spring back once the input stops, like mobile platforms' rubber banding.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct ElasticOverscroll {
+    /// The furthest the contents can be stretched, in logical pixels.
+    pub max: f32,
+    /// How quickly the contents spring back, the fraction of the overscroll recovered per second.
+    pub stiffness: f32,
+}
+
+impl Default for ElasticOverscroll {
+    fn default() -> Self {
+        Self {
+            max: 80.,
+            stiffness: 12.,
+        }
+    }
+}
+
+#[derive(Component, Default)]
+pub(crate) struct Overscroll {
+    // in logical pixels, positive past the end of the viewport
+    offset: Vec2,
+    last_input: Duration,
+    held: bool,
+}
+
+// stretch an overscroll `offset` along one axis by `excess` input, resisting more the further it
+// is already stretched
+fn stretch(offset: f32, excess: f32, max: f32) -> f32 {
+    if max <= 0. {
+        return 0.;
+    }
+    let resistance = OVERSCROLL_RESISTANCE * (1. - offset.abs() / max).max(0.);
+    (offset + excess * resistance).clamp(-max, max)
+}
+
+// add `excess` input beyond the viewport's limits to its overscroll, if it is elastic
+fn add_overscroll(
+    entity: Entity,
+    elastic: Option<&ElasticOverscroll>,
+    overscroll: Option<Mut<Overscroll>>,
+    excess: Vec2,
+    now: Duration,
+    held: bool,
+    commands: &mut Commands,
+) {
+    let Some(&ElasticOverscroll { max, .. }) = elastic else {
+        return;
+    };
+    if excess == Vec2::ZERO && overscroll.is_none() {
+        return;
+    }
+    let update = |overscroll: &mut Overscroll| {
+        overscroll.offset = Vec2::new(
+            stretch(overscroll.offset.x, excess.x, max),
+            stretch(overscroll.offset.y, excess.y, max),
+        );
+        overscroll.last_input = now;
+        overscroll.held = held;
+    };
+    if let Some(mut overscroll) = overscroll {
+        update(&mut overscroll);
+    } else if let Ok(mut entity) = commands.get_entity(entity) {
+        let mut overscroll = Overscroll::default();
+        update(&mut overscroll);
+        entity.try_insert(overscroll);
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn on_scroll_by(
+    scroll_by: Trigger<ScrollBy>,
+    time: Res<Time>,
+    mut viewports: Query<(
+        &mut ScrollPosition,
+        Option<&ComputedNode>,
+        Option<&SmoothScroll>,
+        Option<&mut ScrollMomentum>,
+        Option<&ElasticOverscroll>,
+        Option<&mut Overscroll>,
+    )>,
+    snap_points: SnapPoints,
+    mut commands: Commands,
+) {
+    let entity = scroll_by.target();
+    let &ScrollBy { delta, mode, held } = scroll_by.event();
+    let Ok((mut scroll_position, computed_node, smooth_scroll, momentum, elastic, overscroll_option)) =
+        viewports.get_mut(entity)
+    else {
+        return;
+    };
+    let clamp = |position: Vec2| match computed_node {
+        Some(computed_node) => position.clamp(Vec2::ZERO, max_scroll_offset(computed_node)),
+        None => position,
+    };
+    let position = scroll_position_vec(&scroll_position);
+    // discrete steps move between snap points, continuous gestures snap once they end
+    let (delta, mode) = if !held && let Some([xs, ys]) = snap_points.get(entity) {
+        let base = smooth_scroll.map(|smooth_scroll| smooth_scroll.to).unwrap_or(position);
+        let target = base + delta;
+        let snapped = Vec2::new(snap_step(&xs, base.x, target.x), snap_step(&ys, base.y, target.y));
+        let mode = match mode {
+            ScrollMode::Kinetic { .. } => ScrollMode::SMOOTH,
+            mode => mode,
+        };
+        (snapped - base, mode)
+    } else {
+        (delta, mode)
+    };
+    // smooth scrolling builds on the target of any ongoing animation
+    let base = match mode {
+        ScrollMode::Smooth { .. } => smooth_scroll.map(|smooth_scroll| smooth_scroll.to).unwrap_or(position),
+        _ => position,
+    };
+    let target = clamp(base + delta);
+    add_overscroll(
+        entity,
+        elastic,
+        overscroll_option,
+        base + delta - target,
+        time.elapsed(),
+        held,
+        &mut commands,
+    );
+    let Ok(mut entity_commands) = commands.get_entity(entity) else {
+        return;
+    };
+    match mode {
+        ScrollMode::Instant => {
+            set_scroll_position(&mut scroll_position, target);
+            entity_commands.remove::<(SmoothScroll, ScrollMomentum)>();
+        }
+        ScrollMode::Smooth { duration, easing } => {
+            entity_commands.remove::<ScrollMomentum>().try_insert(SmoothScroll {
+                from: position,
+                to: target,
+                elapsed: Duration::ZERO,
+                duration,
+                easing,
+            });
+        }
+        ScrollMode::Kinetic { friction } => {
+            set_scroll_position(&mut scroll_position, target);
+            let now = time.elapsed();
+            let velocity = match momentum {
+                // only continuous gestures build up velocity
+                Some(momentum) if momentum.held || now.saturating_sub(momentum.last_input) < GESTURE_TIMEOUT => {
+                    let dt = (now - momentum.last_input)
+                        .as_secs_f32()
+                        .max(time.delta_secs())
+                        .max(f32::EPSILON);
+                    momentum.velocity.lerp(delta / dt, 0.5)
+                }
+                _ => Vec2::ZERO,
+            };
+            entity_commands.remove::<SmoothScroll>().try_insert(ScrollMomentum {
+                velocity,
+                friction,
+                last_input: now,
+                held,
+            });
+        }
+    }
+}
+
+fn animate_smooth_scroll(
+    time: Res<Time>,
+    mut smooth_scrolls: Query<(Entity, &mut SmoothScroll, &mut ScrollPosition)>,
+    mut commands: Commands,
+) {
+    for (entity, mut smooth_scroll, mut scroll_position) in smooth_scrolls.iter_mut() {
+        smooth_scroll.elapsed += time.delta();
+        let t = if smooth_scroll.duration.is_zero() {
+            1.
+        } else {
+            (smooth_scroll.elapsed.as_secs_f32() / smooth_scroll.duration.as_secs_f32()).min(1.)
+        };
+        let position = smooth_scroll
+            .from
+            .lerp(smooth_scroll.to, smooth_scroll.easing.sample_clamped(t));
+        set_scroll_position(&mut scroll_position, position);
+        if t >= 1.
+            && let Ok(mut entity) = commands.get_entity(entity)
+        {
+            entity.remove::<SmoothScroll>();
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn apply_scroll_momentum(
+    time: Res<Time>,
+    mut momentums: Query<(
+        Entity,
+        &mut ScrollMomentum,
+        &mut ScrollPosition,
+        Option<&ComputedNode>,
+        Option<&ElasticOverscroll>,
+        Option<&mut Overscroll>,
+    )>,
+    mut commands: Commands,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut momentum, mut scroll_position, computed_node, elastic, overscroll_option) in momentums.iter_mut() {
+        // the gesture is still ongoing
+        if momentum.held || time.elapsed().saturating_sub(momentum.last_input) < GESTURE_TIMEOUT {
+            continue;
+        }
+        let mut position = scroll_position_vec(&scroll_position) + momentum.velocity * dt;
+        if let Some(computed_node) = computed_node {
+            let max = max_scroll_offset(computed_node);
+            let clamped = position.clamp(Vec2::ZERO, max);
+            // stop gliding along axes which hit their limit, bouncing elastic viewports
+            add_overscroll(
+                entity,
+                elastic,
+                overscroll_option,
+                position - clamped,
+                time.elapsed(),
+                false,
+                &mut commands,
+            );
+            if clamped.x != position.x {
+                momentum.velocity.x = 0.;
+            }
+            if clamped.y != position.y {
+                momentum.velocity.y = 0.;
+            }
+            position = clamped;
+        }
+        set_scroll_position(&mut scroll_position, position);
+        let friction = momentum.friction;
+        momentum.velocity *= (-friction * dt).exp();
+        if momentum.velocity.length() < MIN_KINETIC_SPEED
+            && let Ok(mut entity_commands) = commands.get_entity(entity)
+        {
+            entity_commands.remove::<ScrollMomentum>();
+            commands.trigger_targets(
+                SnapScroll {
+                    mode: ScrollMode::SMOOTH,
+                },
+                entity,
+            );
+        }
+    }
+}
+
+fn on_snap_scroll(
+    snap_scroll: Trigger<SnapScroll>,
+    mut scroll_positions: Query<&mut ScrollPosition>,
+    snap_points: SnapPoints,
+    mut commands: Commands,
+) {
+    let entity = snap_scroll.target();
+    let Ok(mut scroll_position) = scroll_positions.get_mut(entity) else {
+        return;
+    };
+    let position = scroll_position_vec(&scroll_position);
+    let Some([xs, ys]) = snap_points.get(entity) else {
+        return;
+    };
+    let target = Vec2::new(
+        nearest(&xs, position.x).unwrap_or(position.x),
+        nearest(&ys, position.y).unwrap_or(position.y),
+    );
+    if target.distance(position) < SNAP_EPSILON {
+        return;
+    }
+    scroll_to_target(entity, &mut scroll_position, target, snap_scroll.mode, &mut commands);
+}
+
+fn relax_overscroll(
+    time: Res<Time>,
+    mut overscrolls: Query<(Entity, &mut Overscroll, Option<&ElasticOverscroll>)>,
+    mut commands: Commands,
+) {
+    for (entity, mut overscroll, elastic) in overscrolls.iter_mut() {
+        // the gesture is still ongoing
+        if overscroll.held || time.elapsed().saturating_sub(overscroll.last_input) < GESTURE_TIMEOUT {
+            continue;
+        }
+        let stiffness = elastic.copied().unwrap_or_default().stiffness;
+        overscroll.offset *= (-stiffness * time.delta_secs()).exp();
+        if (elastic.is_none() || overscroll.offset.length() < MIN_OVERSCROLL)
+            && let Ok(mut entity) = commands.get_entity(entity)
+        {
+            entity.remove::<Overscroll>();
+        }
+    }
+}
+
+// shift the children of overscrolled viewports after layout positions them, which also undoes the
+// shift on the next layout once the viewport settles
+pub(crate) fn apply_overscroll(
+    overscrolls: Query<(&Overscroll, &ComputedNode, &Children)>,
+    mut transforms: Query<&mut Transform>,
+) {
+    for (overscroll, computed_node, children) in overscrolls.iter() {
+        // `Transform`s of UI nodes are in physical pixels
+        let offset = overscroll.offset / computed_node.inverse_scale_factor();
+        for child in children.iter() {
+            if let Ok(mut transform) = transforms.get_mut(child) {
+                transform.translation -= offset.extend(0.);
+            }
+        }
+    }
+}
+
+// move the viewport of `entity` to `target`, animating it there with [`ScrollMode::Smooth`] and
+// jumping there otherwise, interrupting any ongoing animation or glide
+pub(crate) fn scroll_to_target(
+    entity: Entity,
+    scroll_position: &mut ScrollPosition,
+    target: Vec2,
+    mode: ScrollMode,
+    commands: &mut Commands,
+) {
+    let Ok(mut entity_commands) = commands.get_entity(entity) else {
+        return;
+    };
+    entity_commands.remove::<(SmoothScroll, ScrollMomentum)>();
+    match mode {
+        ScrollMode::Smooth { duration, easing } => {
+            entity_commands.try_insert(SmoothScroll {
+                from: scroll_position_vec(scroll_position),
+                to: target,
+                elapsed: Duration::ZERO,
+                duration,
+                easing,
+            });
+        }
+        _ => set_scroll_position(scroll_position, target),
+    }
+}
+
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(Update, scroll_system.run_if(any_with_component::<ScrollEnabled>));
+    app.add_observer(on_scroll_by)
+        .add_observer(on_snap_scroll)
+        .add_systems(
+            Update,
+            (
+                scroll_system.run_if(any_with_component::<ScrollEnabled>),
+                animate_smooth_scroll.run_if(any_with_component::<SmoothScroll>),
+                apply_scroll_momentum.run_if(any_with_component::<ScrollMomentum>),
+                relax_overscroll.run_if(any_with_component::<Overscroll>),
+            ),
+        )
+        .add_systems(
+            PostUpdate,
+            (
+                record_snap_layout_offsets
+                    .after(UiSystem::Layout)
+                    .run_if(any_with_component::<ScrollSnap>),
+                apply_overscroll
+                    .after(UiSystem::Layout)
+                    .before(TransformSystem::TransformPropagate)
+                    .run_if(any_with_component::<Overscroll>),
+            ),
+        );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_picking::backend::HitData;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .add_observer(on_scroll_by)
+            .add_observer(on_snap_scroll)
+            .add_systems(Update, (animate_smooth_scroll, apply_scroll_momentum));
+        app
+    }
+
+    fn advance(app: &mut App, millis: u64) {
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(millis));
+        app.update();
+    }
+
+    fn scroll_by(app: &mut App, entity: Entity, y: f32, mode: ScrollMode) {
+        app.world_mut().trigger_targets(
+            ScrollBy {
+                delta: Vec2::new(0., y),
+                mode,
+                held: false,
+            },
+            entity,
+        );
+        app.world_mut().flush();
+    }
+
+    fn offset_y(app: &App, entity: Entity) -> f32 {
+        app.world().get::<ScrollPosition>(entity).unwrap().offset_y
+    }
+
+    #[test]
+    fn smooth_scroll_animates_to_its_extended_target() {
+        let mut app = app();
+        let entity = app.world_mut().spawn(ScrollPosition::default()).id();
+        let mode = ScrollMode::Smooth {
+            duration: Duration::from_millis(100),
+            easing: EaseFunction::Linear,
+        };
+        scroll_by(&mut app, entity, 100., mode);
+        assert_eq!(offset_y(&app, entity), 0.);
+        advance(&mut app, 50);
+        assert!((offset_y(&app, entity) - 50.).abs() < 0.01);
+        // scrolling mid animation extends its target rather than restarting from the current one
+        scroll_by(&mut app, entity, 100., mode);
+        advance(&mut app, 50);
+        assert!((offset_y(&app, entity) - 125.).abs() < 0.01);
+        advance(&mut app, 50);
+        assert!((offset_y(&app, entity) - 200.).abs() < 0.01);
+        assert!(app.world().get::<SmoothScroll>(entity).is_none());
+    }
+
+    #[test]
+    fn kinetic_scroll_glides_after_the_gesture_ends() {
+        let mut app = app();
+        let entity = app.world_mut().spawn(ScrollPosition::default()).id();
+        let mode = ScrollMode::Kinetic { friction: 4. };
+        scroll_by(&mut app, entity, 10., mode);
+        advance(&mut app, 10);
+        scroll_by(&mut app, entity, 10., mode);
+        assert_eq!(offset_y(&app, entity), 20.);
+        // still within the gesture, so no glide yet
+        advance(&mut app, 10);
+        assert_eq!(offset_y(&app, entity), 20.);
+        let mut previous = 20.;
+        for _ in 0..100 {
+            advance(&mut app, 100);
+            let offset = offset_y(&app, entity);
+            assert!(offset >= previous);
+            previous = offset;
+            if app.world().get::<ScrollMomentum>(entity).is_none() {
+                break;
+            }
+        }
+        assert!(app.world().get::<ScrollMomentum>(entity).is_none());
+        // a velocity of 500 pixels per second decaying by friction 4 glides roughly 125 pixels
+        assert!((100. ..150.).contains(&(previous - 20.)), "glided {}", previous - 20.);
+    }
+
+    #[derive(Resource, Default)]
+    struct Scrolled(Vec<Entity>);
+
+    #[test]
+    fn wheel_events_go_to_the_topmost_hovered_scrollable() {
+        let mut world = World::new();
+        world.init_resource::<Events<MouseWheel>>();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<Scrolled>();
+        world.add_observer(|event: Trigger<MouseWheel>, mut scrolled: ResMut<Scrolled>| {
+            // skip the broadcast to global listeners
+            if event.target() != Entity::PLACEHOLDER {
+                scrolled.0.push(event.target());
+            }
+        });
+        let parent = world.spawn_empty().id();
+        let [below, above] = [(); 2].map(|_| world.spawn((ScrollEnabled, ScrollOnHover, ChildOf(parent))).id());
+        let camera = world.spawn_empty().id();
+        let mut hover_map = HoverMap::default();
+        hover_map.insert(
+            PointerId::Mouse,
+            [(below, 1.), (above, 0.), (parent, 2.)]
+                .map(|(entity, depth)| (entity, HitData::new(camera, depth, None, None)))
+                .into_iter()
+                .collect(),
+        );
+        world.insert_resource(hover_map);
+        world.send_event(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.,
+            y: 1.,
+            window: Entity::PLACEHOLDER,
+        });
+        world.run_system_once(scroll_system).unwrap();
+        assert_eq!(world.resource::<Scrolled>().0, [above]);
+    }
+
+    #[test]
+    fn snap_points_follow_the_content_rather_than_the_scroll_position() {
+        let mut app = app();
+        let viewport = app
+            .world_mut()
+            .spawn((
+                Node {
+                    overflow: Overflow::scroll_y(),
+                    ..default()
+                },
+                ComputedNode {
+                    size: Vec2::splat(100.),
+                    content_size: Vec2::new(100., 400.),
+                    inverse_scale_factor: 1.,
+                    ..default()
+                },
+                GlobalTransform::from_translation(Vec3::new(50., 50., 0.)),
+                ScrollSnap::Start,
+                ScrollPosition::default(),
+            ))
+            .id();
+        for i in 0..4 {
+            app.world_mut().spawn((
+                ComputedNode {
+                    size: Vec2::splat(100.),
+                    inverse_scale_factor: 1.,
+                    ..default()
+                },
+                GlobalTransform::from_translation(Vec3::new(50., 50. + 100. * i as f32, 0.)),
+                ChildOf(viewport),
+            ));
+        }
+        app.world_mut().run_system_once(record_snap_layout_offsets).unwrap();
+        // without a layout between them, the children are still where they were before the first step
+        scroll_by(&mut app, viewport, 40., ScrollMode::Instant);
+        assert_eq!(offset_y(&app, viewport), 100.);
+        scroll_by(&mut app, viewport, 40., ScrollMode::Instant);
+        assert_eq!(offset_y(&app, viewport), 200.);
+        app.world_mut().get_mut::<ScrollPosition>(viewport).unwrap().offset_y = 140.;
+        app.world_mut().trigger_targets(
+            SnapScroll {
+                mode: ScrollMode::Instant,
+            },
+            viewport,
+        );
+        app.world_mut().flush();
+        assert_eq!(offset_y(&app, viewport), 100.);
+    }
 }
@@ -10,7 +10,7 @@ use bevy_tasks::Task;
 use bevy_utils::prelude::*;
 use futures_signals::{
     signal::{Mutable, Signal, SignalExt},
-    signal_vec::{MutableVec, SignalVec, SignalVecExt, VecDiff},
+    signal_vec::{MutableVec, MutableVecLockMut, SignalVec, SignalVecExt, VecDiff},
 };
 use haalka_futures_signals_ext::{Future, MutableExt};
 
@@ -286,31 +286,8 @@ impl NodeBuilder {
                             }
                             VecDiff::Move { old_index, new_index } => {
                                 async_world().apply(move |world: &mut World| {
-                                    let mut children_lock = children_entities.lock_mut();
-                                    children_lock.swap(old_index, new_index);
-                                    // porting the swap implementation above
-                                    fn move_from_to(parent: &mut EntityWorldMut, children_entities: &[Entity], old_index: usize, new_index: usize) {
-                                        if old_index != new_index && let Some(old_entity) = children_entities.get(old_index).copied() {
-                                            parent.remove_children(&[old_entity]);
-                                            parent.insert_children(new_index, &[old_entity]);
-                                        }
-                                    }
-                                    fn swap(parent: &mut EntityWorldMut, children_entities: &[Entity], a: usize, b: usize) {
-                                        move_from_to(parent, children_entities, a, b);
-                                        match a.cmp(&b) {
-                                            std::cmp::Ordering::Less => {
-                                                move_from_to(parent, children_entities, b - 1, a);
-                                            }
-                                            std::cmp::Ordering::Greater => {
-                                                move_from_to(parent, children_entities, b + 1, a)
-                                            }
-                                            _ => {}
-                                        }
-                                    }
-                                    if let Ok(mut parent) = world.get_entity_mut(parent) {
-                                        let offset = offset(block, &child_block_populations.lock().unwrap());
-                                        swap(&mut parent, children_lock.as_slice(), offset + old_index, offset + new_index);
-                                    }
+                                    let offset = offset(block, &child_block_populations.lock().unwrap());
+                                    move_child(world, parent, offset, &mut children_entities.lock_mut(), old_index, new_index);
                                 })
                                 .await;
                             }
@@ -429,3 +406,57 @@ impl TaskHolder {
 fn offset(i: usize, child_block_populations: &[usize]) -> usize {
     child_block_populations[0..i].iter().sum()
 }
+
+// `VecDiff::Move` removes the child at `old_index` and then inserts it at `new_index` of the
+// resulting vec
+fn move_child(
+    world: &mut World,
+    parent: Entity,
+    offset: usize,
+    children: &mut MutableVecLockMut<Entity>,
+    old_index: usize,
+    new_index: usize,
+) {
+    if old_index == new_index || old_index >= children.len() {
+        return;
+    }
+    let child = children.remove(old_index);
+    let new_index = new_index.min(children.len());
+    children.insert(new_index, child);
+    if let Ok(mut parent) = world.get_entity_mut(parent) {
+        parent.remove_children(&[child]);
+        parent.insert_children(offset + new_index, &[child]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn children(world: &World, parent: Entity) -> Vec<Entity> {
+        world.get::<Children>(parent).map(|children| children.to_vec()).unwrap_or_default()
+    }
+
+    #[test]
+    fn move_reorders_children_forward_and_backward() {
+        let mut world = World::new();
+        let parent = world.spawn_empty().id();
+        // a static child before the reactive block, which offsets it by one
+        let header = world.spawn(ChildOf(parent)).id();
+        let items = (0..4).map(|_| world.spawn(ChildOf(parent)).id()).collect::<Vec<_>>();
+        let children_entities = MutableVec::new_with_values(items.clone());
+        let [a, b, c, d] = items[..] else { unreachable!() };
+
+        move_child(&mut world, parent, 1, &mut children_entities.lock_mut(), 0, 2);
+        assert_eq!(*children_entities.lock_ref(), [b, c, a, d]);
+        assert_eq!(children(&world, parent), [header, b, c, a, d]);
+
+        move_child(&mut world, parent, 1, &mut children_entities.lock_mut(), 3, 1);
+        assert_eq!(*children_entities.lock_ref(), [b, d, c, a]);
+        assert_eq!(children(&world, parent), [header, b, d, c, a]);
+
+        move_child(&mut world, parent, 1, &mut children_entities.lock_mut(), 1, 3);
+        assert_eq!(*children_entities.lock_ref(), [b, c, a, d]);
+        assert_eq!(children(&world, parent), [header, b, c, a, d]);
+    }
+}
@@ -0,0 +1,409 @@
+//! Numeric input widget two-way bound to a [`Mutable`], see [`NumberInput`].
+
+use std::{fmt::Display, str::FromStr, sync::Arc};
+
+use bevy_color::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_picking::prelude::*;
+use bevy_ui::prelude::*;
+use bevy_ui_text_input::TextInputMode;
+use bevy_window::SystemCursorIcon;
+use bevy_winit::cursor::CursorIcon;
+use futures_signals::signal::Mutable;
+
+use super::{
+    el::El,
+    element::{ElementWrapper, Nameable, UiRootable},
+    global_event_aware::GlobalEventAware,
+    pointer_event_aware::{CursorOnHoverable, PointerEventAware},
+    raw::RawElWrapper,
+    row::Row,
+    text_input::{TextInput, set_text},
+    utils::clone,
+};
+
+// how far, in logical pixels, the pointer must be dragged across a step button to scrub by one step
+const SCRUB_PIXELS_PER_STEP: f32 = 4.;
+
+/// Numbers which can be edited with a [`NumberInput`].
+pub trait NumberInputValue: Copy + Default + PartialOrd + Display + FromStr + Send + Sync + 'static {
+    /// Whether this type only holds whole numbers.
+    const INTEGER: bool;
+    /// Whether this type holds negative numbers.
+    const SIGNED: bool;
+    /// The default step.
+    const ONE: Self;
+    /// Add `steps` times `step`, saturating at this type's bounds.
+    fn step(self, step: Self, steps: i64) -> Self;
+    /// Round to `precision` digits after the decimal point.
+    fn round(self, precision: usize) -> Self;
+}
+
+macro_rules! impl_number_input_value_integer {
+    ($signed:literal: $($type:ty),*) => {
+        $(
+            impl NumberInputValue for $type {
+                const INTEGER: bool = true;
+                const SIGNED: bool = $signed;
+                const ONE: Self = 1;
+
+                fn step(self, step: Self, steps: i64) -> Self {
+                    // `i128` holds every value of the other integer types, so this is exact up to
+                    // the saturation
+                    (step as i128)
+                        .saturating_mul(steps as i128)
+                        .saturating_add(self as i128)
+                        .clamp(<$type>::MIN as i128, <$type>::MAX as i128) as $type
+                }
+
+                fn round(self, _: usize) -> Self {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_number_input_value_float {
+    ($($type:ty),*) => {
+        $(
+            impl NumberInputValue for $type {
+                const INTEGER: bool = false;
+                const SIGNED: bool = true;
+                const ONE: Self = 1.;
+
+                fn step(self, step: Self, steps: i64) -> Self {
+                    self + step * steps as $type
+                }
+
+                fn round(self, precision: usize) -> Self {
+                    let scale = 10f64.powi(precision as i32);
+                    ((self as f64 * scale).round() / scale) as $type
+                }
+            }
+        )*
+    };
+}
+
+impl_number_input_value_integer!(true: i8, i16, i32, i64, isize);
+impl_number_input_value_integer!(false: u8, u16, u32, u64, usize);
+impl_number_input_value_float!(f32, f64);
+
+#[allow(clippy::type_complexity)]
+struct Config<T> {
+    min: Option<T>,
+    max: Option<T>,
+    step: T,
+    precision: Option<usize>,
+    format: Option<Arc<dyn Fn(T) -> String + Send + Sync>>,
+    parse: Option<Arc<dyn Fn(&str) -> Option<T> + Send + Sync>>,
+    buttons: bool,
+    wheel: bool,
+}
+
+impl<T: NumberInputValue> Config<T> {
+    fn clamp(&self, mut value: T) -> T {
+        if let Some(min) = self.min
+            && value < min
+        {
+            value = min;
+        }
+        if let Some(max) = self.max
+            && value > max
+        {
+            value = max;
+        }
+        value
+    }
+
+    fn step(&self, value: T, steps: i64) -> T {
+        let mut stepped = value.step(self.step, steps);
+        // avoid accumulating floating point noise, e.g. `0.1 + 0.2`
+        if let Some(precision) = self.precision {
+            stepped = stepped.round(precision);
+        }
+        self.clamp(stepped)
+    }
+
+    fn format(&self, value: T) -> String {
+        match (&self.format, self.precision) {
+            (Some(format), _) => format(value),
+            (None, Some(precision)) if !T::INTEGER => format!("{value:.precision$}"),
+            _ => value.to_string(),
+        }
+    }
+
+    fn parse(&self, text: &str) -> Option<T> {
+        match &self.parse {
+            Some(parse) => parse(text),
+            None => text.trim().parse().ok(),
+        }
+    }
+}
+
+/// Text input for numbers, two-way bound to a [`Mutable`] via
+/// [`.value_sync`](NumberInput::value_sync). The value can be typed, stepped with the decrement and
+/// increment buttons or the mouse wheel, or scrubbed by dragging horizontally across either button,
+/// and is kept within [`.min`](NumberInput::min) and [`.max`](NumberInput::max).
+///
+/// Text which doesn't parse, or parses to a value out of range, is left alone while it is being
+/// typed and never reaches the bound value; on submit or blur it is clamped into range or reverted
+/// to the bound value.
+pub struct NumberInput<T: NumberInputValue> {
+    el: Row<Node>,
+    text_input: TextInput,
+    decrement: El<Node>,
+    increment: El<Node>,
+    value: Mutable<T>,
+    config: Config<T>,
+}
+
+impl<T: NumberInputValue> Default for NumberInput<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn step_button(label: &str) -> El<Node> {
+    El::<Node>::new()
+        .with_node(|mut node| {
+            node.width = Val::Px(24.);
+            node.justify_content = JustifyContent::Center;
+            node.align_items = AlignItems::Center;
+        })
+        .background_color(BackgroundColor(Color::srgba(1., 1., 1., 0.1)))
+        .cursor(CursorIcon::System(SystemCursorIcon::EwResize))
+        .update_raw_el(|raw_el| raw_el.insert(Pickable::default()))
+        .child(El::<Text>::new().text(Text::new(label)))
+}
+
+impl<T: NumberInputValue> NumberInput<T> {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {
+            el: Row::<Node>::new().with_node(|mut node| node.column_gap = Val::Px(2.)),
+            text_input: TextInput::new()
+                .with_node(|mut node| node.flex_grow = 1.)
+                .with_text_input_node(|mut text_input_node| text_input_node.mode = TextInputMode::SingleLine),
+            decrement: step_button("-"),
+            increment: step_button("+"),
+            value: Mutable::new(T::default()),
+            config: Config {
+                min: None,
+                max: None,
+                step: T::ONE,
+                precision: None,
+                format: None,
+                parse: None,
+                buttons: true,
+                wheel: true,
+            },
+        }
+    }
+
+    /// Two-way bind this input to a [`Mutable`]; changes to it are formatted into the text and
+    /// valid text is parsed into it.
+    pub fn value_sync(mut self, value: Mutable<T>) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Set the smallest allowed value.
+    pub fn min(mut self, min_option: impl Into<Option<T>>) -> Self {
+        self.config.min = min_option.into();
+        self
+    }
+
+    /// Set the largest allowed value.
+    pub fn max(mut self, max_option: impl Into<Option<T>>) -> Self {
+        self.config.max = max_option.into();
+        self
+    }
+
+    /// Set how much the buttons, mouse wheel, and scrubbing change the value by, `1` by default.
+    pub fn step(mut self, step: T) -> Self {
+        self.config.step = step;
+        self
+    }
+
+    /// Set the number of digits shown after the decimal point, which stepping also rounds to.
+    pub fn precision(mut self, precision_option: impl Into<Option<usize>>) -> Self {
+        self.config.precision = precision_option.into();
+        self
+    }
+
+    /// Format the value with a custom function, e.g. to add a unit; pair it with
+    /// [`.parse`](NumberInput::parse) if its output doesn't parse as `T`.
+    pub fn format(mut self, format: impl Fn(T) -> String + Send + Sync + 'static) -> Self {
+        self.config.format = Some(Arc::new(format));
+        self
+    }
+
+    /// Parse typed text with a custom function, returning [`None`] for invalid text; also lifts
+    /// the default restriction to digits, signs, and decimal points.
+    pub fn parse(mut self, parse: impl Fn(&str) -> Option<T> + Send + Sync + 'static) -> Self {
+        self.config.parse = Some(Arc::new(parse));
+        self
+    }
+
+    /// Set whether the decrement and increment buttons are shown, `true` by default.
+    pub fn buttons(mut self, buttons: bool) -> Self {
+        self.config.buttons = buttons;
+        self
+    }
+
+    /// Set whether scrolling the mouse wheel over this input steps its value, `true` by default.
+    pub fn wheel(mut self, wheel: bool) -> Self {
+        self.config.wheel = wheel;
+        self
+    }
+
+    /// Modify the [`Row`] containing the text input and buttons.
+    pub fn row(mut self, f: impl FnOnce(Row<Node>) -> Row<Node>) -> Self {
+        self.el = f(self.el);
+        self
+    }
+
+    /// Modify the [`TextInput`], e.g. to set its font.
+    pub fn text_input(mut self, f: impl FnOnce(TextInput) -> TextInput) -> Self {
+        self.text_input = f(self.text_input);
+        self
+    }
+
+    /// Modify the decrement button.
+    pub fn decrement_button(mut self, f: impl FnOnce(El<Node>) -> El<Node>) -> Self {
+        self.decrement = f(self.decrement);
+        self
+    }
+
+    /// Modify the increment button.
+    pub fn increment_button(mut self, f: impl FnOnce(El<Node>) -> El<Node>) -> Self {
+        self.increment = f(self.increment);
+        self
+    }
+}
+
+fn with_stepping<T: NumberInputValue>(
+    button: El<Node>,
+    direction: i64,
+    value: Mutable<T>,
+    config: Arc<Config<T>>,
+) -> El<Node> {
+    let scrub_start = Mutable::new(value.get());
+    // a click ends every drag over the same button, which shouldn't also step
+    let scrubbed = Mutable::new(false);
+    button
+        .on_drag_start_local(clone!((value, scrub_start, scrubbed) move |_| {
+            scrub_start.set(value.get());
+            scrubbed.set(false);
+        }))
+        .on_drag_local(clone!((value, config, scrubbed) move |local| {
+            let steps = (local.distance.x / SCRUB_PIXELS_PER_STEP).trunc() as i64;
+            if steps != 0 {
+                scrubbed.set(true);
+            }
+            if scrubbed.get() {
+                value.set_neq(config.step(scrub_start.get(), steps));
+            }
+        }))
+        .on_click(move || {
+            if !scrubbed.replace(false) {
+                value.set_neq(config.step(value.get(), direction));
+            }
+        })
+}
+
+impl<T: NumberInputValue> ElementWrapper for NumberInput<T> {
+    type EL = Row<Node>;
+    fn element_mut(&mut self) -> &mut Self::EL {
+        &mut self.el
+    }
+
+    fn into_el(self) -> Self::EL {
+        let Self {
+            el,
+            mut text_input,
+            decrement,
+            increment,
+            value,
+            config,
+        } = self;
+        if config.parse.is_none() {
+            text_input = text_input
+                .filter(|c| c.is_ascii_digit() || T::SIGNED && c == '-' || !T::INTEGER && c == '.');
+        }
+        let config = Arc::new(config);
+        let (buttons, wheel) = (config.buttons, config.wheel);
+        // the value most recently parsed from typed text, so it isn't formatted back over that text
+        let typed = Mutable::new(None);
+        let text = Mutable::new(String::new());
+        let commit = clone!((value, typed, config) move |text: &str| {
+            let committed = config.parse(text).map(|parsed| config.clamp(parsed)).unwrap_or(value.get());
+            typed.set(None);
+            // not `set_neq`, so the text is reformatted even if the value didn't change
+            value.set(committed);
+        });
+        let text_input = text_input
+            .update_raw_el(clone!((value, typed, config) move |raw_el| {
+                raw_el.on_signal_with_system(
+                    value.signal(),
+                    move |In((entity, value)): In<(Entity, T)>, mut commands: Commands| {
+                        if typed.replace(None) != Some(value) {
+                            let text = config.format(value);
+                            commands.entity(entity).queue(move |mut entity: EntityWorldMut| set_text(&mut entity, text));
+                        }
+                    },
+                )
+            }))
+            .on_change(clone!((value, typed, config, text) move |new| {
+                // text formatted from the value itself, which may have been rounded
+                if new != config.format(value.get())
+                    && let Some(parsed) = config.parse(&new)
+                    && config.clamp(parsed) == parsed
+                    && parsed != value.get()
+                {
+                    typed.set(Some(parsed));
+                    value.set(parsed);
+                }
+                text.set(new);
+            }))
+            .on_submit(clone!((commit) move |submitted| commit(&submitted)))
+            .on_focused_change(move |focused| {
+                if !focused {
+                    commit(&text.lock_ref());
+                }
+            });
+        el.update_raw_el(clone!((value, config) move |raw_el| {
+            raw_el.insert(Pickable::default()).observe(move |scroll: Trigger<Pointer<Scroll>>| {
+                let y = scroll.event().y;
+                if wheel && y != 0. {
+                    value.set_neq(config.step(value.get(), y.signum() as i64));
+                }
+            })
+        }))
+        .item(buttons.then(|| with_stepping(decrement, -1, value.clone(), config.clone())))
+        .item(text_input)
+        .item(buttons.then(|| with_stepping(increment, 1, value, config)))
+    }
+}
+
+impl<T: NumberInputValue> CursorOnHoverable for NumberInput<T> {}
+impl<T: NumberInputValue> GlobalEventAware for NumberInput<T> {}
+impl<T: NumberInputValue> Nameable for NumberInput<T> {}
+impl<T: NumberInputValue> PointerEventAware for NumberInput<T> {}
+impl<T: NumberInputValue> UiRootable for NumberInput<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_are_exact_and_saturate() {
+        // beyond `f64`'s 53 bits of mantissa
+        assert_eq!((u64::MAX - 10).step(3, 2), u64::MAX - 4);
+        assert_eq!((u64::MAX - 10).step(3, 4), u64::MAX);
+        assert_eq!(2u8.step(1, -5), 0);
+        assert_eq!(i64::MIN.step(i64::MAX, i64::MAX), i64::MAX);
+        assert_eq!(NumberInputValue::round(0.1f64.step(0.2, 1), 2), 0.3);
+    }
+}
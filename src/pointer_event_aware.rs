@@ -2,7 +2,9 @@
 //! hover, click, and press, see [`PointerEventAware`].
 
 use std::{
+    fmt::Debug,
     future::Future,
+    iter, mem,
     ops::Not,
     sync::{Arc, OnceLock},
     time::Duration,
@@ -11,15 +13,19 @@ use std::{
 use apply::Apply;
 use bevy_app::prelude::*;
 use bevy_derive::*;
-use bevy_ecs::prelude::*;
+use bevy_ecs::{prelude::*, system::SystemParam};
 use bevy_log::prelude::*;
+use bevy_math::prelude::*;
 use bevy_picking::{
     backend::prelude::*,
     hover::{HoverMap, PickingInteraction},
     pointer::PointerMap,
     prelude::*,
+    PickSet,
 };
 use bevy_reflect::prelude::*;
+use bevy_render::camera::NormalizedRenderTarget;
+use bevy_ui::prelude::*;
 use bevy_utils::prelude::*;
 use bevy_window::{prelude::*, *};
 use bevy_winit::cursor::CursorIcon;
@@ -30,8 +36,9 @@ use haalka_futures_signals_ext::SignalExtBool;
 use super::{
     element::UiRoot,
     global_event_aware::GlobalEventAware,
-    raw::{observe, register_system, utils::remove_system_holder_on_remove},
+    raw::{RawHaalkaEl, observe, register_system, utils::remove_system_holder_on_remove},
     utils::sleep,
+    viewport_mutable::PhysicalRect,
 };
 
 /// Enables reacting to pointer events like hover, click, and press. Port of [MoonZoon](https://github.com/MoonZoon/MoonZoon)'s [`PointerEventAware`](https://github.com/MoonZoon/MoonZoon/blob/main/crates/zoon/src/element/ability/pointer_event_aware.rs).
@@ -356,6 +363,198 @@ pub trait PointerEventAware: GlobalEventAware {
     fn pressed_sync(self, pressed: Mutable<bool>) -> Self {
         self.on_pressed_change(move |cur| pressed.set_neq(cur))
     }
+
+    /// When the pointer moves over this element or its descendants, run a [`System`] which takes
+    /// [`In`](`System::In`) this element's [`Entity`] and the [`LocalPointer`] in this element's
+    /// own space. This method can be called repeatedly to register many such handlers.
+    fn on_pointer_move_local_with_system<Marker>(
+        self,
+        handler: impl IntoSystem<In<(Entity, LocalPointer)>, (), Marker> + Send + 'static,
+    ) -> Self {
+        self.update_raw_el(|raw_el| {
+            observe_local_pointer::<Move, _>(raw_el, |move_| Some((move_.delta, Vec2::ZERO)), handler)
+        })
+    }
+
+    /// When the pointer moves over this element or its descendants, run a function with the
+    /// pointer's position relative to the top left corner of this element, in logical pixels.
+    fn on_pointer_move_local(self, mut handler: impl FnMut(Vec2) + Send + Sync + 'static) -> Self {
+        self.on_pointer_move_local_with_system(move |In((_, local)): In<(_, LocalPointer)>| handler(local.position))
+    }
+
+    /// When the pointer moves over this element or its descendants, run a function with the
+    /// pointer's position normalized to this element's size, where `(0, 0)` is the top left
+    /// corner and `(1, 1)` is the bottom right corner.
+    fn on_pointer_move_normalized(self, mut handler: impl FnMut(Vec2) + Send + Sync + 'static) -> Self {
+        self.on_pointer_move_local_with_system(move |In((_, local)): In<(_, LocalPointer)>| handler(local.normalized))
+    }
+
+    /// Sync a [`Mutable`] with the pointer's position relative to the top left corner of this
+    /// element, in logical pixels; [`None`] when this element is not hovered.
+    fn pointer_position_sync(self, position: Mutable<Option<Vec2>>) -> Self {
+        self.on_pointer_move_local(clone!((position) move |local| position.set_neq(Some(local))))
+            .on_hovered_change(move |is_hovered| {
+                if !is_hovered {
+                    position.set_neq(None);
+                }
+            })
+    }
+
+    /// Sync a [`Mutable`] with the pointer's position normalized to this element's size, where
+    /// `(0, 0)` is the top left corner and `(1, 1)` is the bottom right corner; [`None`] when
+    /// this element is not hovered.
+    fn pointer_position_normalized_sync(self, normalized: Mutable<Option<Vec2>>) -> Self {
+        self.on_pointer_move_normalized(clone!((normalized) move |local| normalized.set_neq(Some(local))))
+            .on_hovered_change(move |is_hovered| {
+                if !is_hovered {
+                    normalized.set_neq(None);
+                }
+            })
+    }
+
+    /// When a primary button drag of this element or its descendants starts, run a [`System`]
+    /// which takes [`In`](`System::In`) this element's [`Entity`] and the [`LocalPointer`] in
+    /// this element's own space. This method can be called repeatedly to register many such
+    /// handlers.
+    fn on_drag_start_local_with_system<Marker>(
+        self,
+        handler: impl IntoSystem<In<(Entity, LocalPointer)>, (), Marker> + Send + 'static,
+    ) -> Self {
+        self.update_raw_el(|raw_el| {
+            observe_local_pointer::<DragStart, _>(
+                raw_el,
+                |drag_start| matches!(drag_start.button, PointerButton::Primary).then_some((Vec2::ZERO, Vec2::ZERO)),
+                handler,
+            )
+        })
+    }
+
+    /// When a primary button drag of this element or its descendants starts, run a function with
+    /// the [`LocalPointer`] in this element's own space.
+    fn on_drag_start_local(self, mut handler: impl FnMut(LocalPointer) + Send + Sync + 'static) -> Self {
+        self.on_drag_start_local_with_system(move |In((_, local))| handler(local))
+    }
+
+    /// While this element or its descendants are being dragged with the primary button, run a
+    /// [`System`] which takes [`In`](`System::In`) this element's [`Entity`] and the
+    /// [`LocalPointer`] in this element's own space. This method can be called repeatedly to
+    /// register many such handlers.
+    fn on_drag_local_with_system<Marker>(
+        self,
+        handler: impl IntoSystem<In<(Entity, LocalPointer)>, (), Marker> + Send + 'static,
+    ) -> Self {
+        self.update_raw_el(|raw_el| {
+            observe_local_pointer::<Drag, _>(
+                raw_el,
+                |drag| matches!(drag.button, PointerButton::Primary).then_some((drag.delta, drag.distance)),
+                handler,
+            )
+        })
+    }
+
+    /// While this element or its descendants are being dragged with the primary button, run a
+    /// function with the [`LocalPointer`] in this element's own space.
+    fn on_drag_local(self, mut handler: impl FnMut(LocalPointer) + Send + Sync + 'static) -> Self {
+        self.on_drag_local_with_system(move |In((_, local))| handler(local))
+    }
+
+    /// When a primary button drag of this element or its descendants ends, run a [`System`] which
+    /// takes [`In`](`System::In`) this element's [`Entity`] and the [`LocalPointer`] in this
+    /// element's own space. This method can be called repeatedly to register many such handlers.
+    fn on_drag_end_local_with_system<Marker>(
+        self,
+        handler: impl IntoSystem<In<(Entity, LocalPointer)>, (), Marker> + Send + 'static,
+    ) -> Self {
+        self.update_raw_el(|raw_el| {
+            observe_local_pointer::<DragEnd, _>(
+                raw_el,
+                |drag_end| matches!(drag_end.button, PointerButton::Primary).then_some((Vec2::ZERO, drag_end.distance)),
+                handler,
+            )
+        })
+    }
+
+    /// When a primary button drag of this element or its descendants ends, run a function with
+    /// the [`LocalPointer`] in this element's own space.
+    fn on_drag_end_local(self, mut handler: impl FnMut(LocalPointer) + Send + Sync + 'static) -> Self {
+        self.on_drag_end_local_with_system(move |In((_, local))| handler(local))
+    }
+}
+
+/// Pointer data in an element's own space, i.e. relative to the top left corner of its
+/// [`ComputedNode`], in logical pixels, accounting for [`UiScale`].
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct LocalPointer {
+    /// Position of the pointer relative to the top left corner of the element.
+    pub position: Vec2,
+    /// [`position`](Self::position) normalized to the element's size, where `(0, 0)` is the top
+    /// left corner and `(1, 1)` is the bottom right corner; not clamped, e.g. when dragging
+    /// outside the element.
+    pub normalized: Vec2,
+    /// Change in position since the last event; zero for drag starts and ends.
+    pub delta: Vec2,
+    /// Total distance travelled since the drag started; zero for moves and drag starts.
+    pub distance: Vec2,
+}
+
+/// Use to convert window space pointer data into an element's own space, see [`LocalPointer`].
+#[derive(SystemParam)]
+pub struct ElementSpace<'w, 's> {
+    physical_rect: PhysicalRect<'w, 's>,
+    ui_scale: Res<'w, UiScale>,
+}
+
+impl ElementSpace<'_, '_> {
+    /// Get the `entity`'s [`Rect`] in logical pixels, accounting for [`UiScale`], i.e. in the same
+    /// units as [`Val::Px`].
+    pub fn rect(&self, entity: Entity) -> Option<Rect> {
+        self.physical_rect.logical(entity)
+    }
+
+    /// Convert a window space pointer `position`, `delta`, and `distance` (in logical pixels, e.g.
+    /// from a [`Pointer`] event) into the `entity`'s own space.
+    pub fn get(&self, entity: Entity, position: Vec2, delta: Vec2, distance: Vec2) -> Option<LocalPointer> {
+        let rect = self.rect(entity)?;
+        let size = rect.size();
+        // pointer locations are in logical window pixels, which do not account for `UiScale`
+        let ui_scale = self.ui_scale.0;
+        let position = position / ui_scale - rect.min;
+        let normalized = if size.x > 0. && size.y > 0. {
+            position / size
+        } else {
+            Vec2::ZERO
+        };
+        Some(LocalPointer {
+            position,
+            normalized,
+            delta: delta / ui_scale,
+            distance: distance / ui_scale,
+        })
+    }
+}
+
+fn observe_local_pointer<E: Debug + Clone + Reflect, Marker>(
+    raw_el: RawHaalkaEl,
+    // `None` skips the event, otherwise the window space delta and distance
+    motion: fn(&E) -> Option<(Vec2, Vec2)>,
+    handler: impl IntoSystem<In<(Entity, LocalPointer)>, (), Marker> + Send + 'static,
+) -> RawHaalkaEl {
+    let system_holder = Arc::new(OnceLock::new());
+    raw_el
+        .insert(Pickable::default())
+        .on_spawn(clone!((system_holder) move |world, entity| {
+            let system = register_system(world, handler);
+            let _ = system_holder.set(system);
+            observe(world, entity, move |event: Trigger<Pointer<E>>, element_space: ElementSpace, mut commands: Commands| {
+                let entity = event.target();
+                if let Some((delta, distance)) = motion(&event.event)
+                    && let Some(local) = element_space.get(entity, event.pointer_location.position, delta, distance)
+                {
+                    commands.run_system_with(system, (entity, local));
+                }
+            });
+        }))
+        .apply(remove_system_holder_on_remove(system_holder))
 }
 
 #[derive(Component, Deref, DerefMut)]
@@ -508,15 +707,16 @@ pub trait CursorOnHoverable: PointerEventAware {
                      cursor_on_hovers: Query<&CursorOnHover>,
                      disabled: Query<&Disabled>,
                      cursor_over_disabled_option: Option<Res<CursorOnHoverDisabled>>,
+                     mut cursor_stack: ResMut<CursorStack>,
                      mut commands: Commands| {
                         let entity = event.target();
                         if let Ok(CursorOnHover(cursor_option)) = cursor_on_hovers.get(entity).cloned() {
                             if cursor_over_disabled_option.is_none() {
                                 if disabled.contains(entity).not() {
-                                    commands.trigger(SetCursor(cursor_option));
+                                    cursor_stack.push(entity, CursorPriority::HOVERED, cursor_option);
                                 }
                             } else {
-                                commands.insert_resource(QueuedCursor(cursor_option));
+                                commands.insert_resource(QueuedCursor(entity, cursor_option));
                             }
                         }
                     },
@@ -542,8 +742,10 @@ pub trait CursorOnHoverable: PointerEventAware {
                           hover_map: Res<HoverMap>,
                           mut pointer_over: EventWriter<Pointer<Over>>,
                           child_ofs: Query<&ChildOf>,
+                          mut cursor_stack: ResMut<CursorStack>,
                           mut commands: Commands| {
                         let entity = event.target();
+                        cursor_stack.remove(entity, CursorPriority::HOVERED);
                         if let Ok(mut entity) = commands.get_entity(entity) {
                             entity.remove::<CursorOverPropagationStopped>();
                         }
@@ -583,9 +785,23 @@ pub trait CursorOnHoverable: PointerEventAware {
                         }
                     },
                 )
-                .on_event_with_system_stop_propagation::<Pointer<Out>, _>(|In((entity, _)), mut commands: Commands| {
-                    if let Ok(mut entity) = commands.get_entity(entity) {
-                        entity.remove::<CursorOver>();
+                .on_event_with_system_stop_propagation::<Pointer<Out>, _>(
+                    |In((entity, _)),
+                     cursor_over_disabled_option: Option<Res<CursorOnHoverDisabled>>,
+                     mut cursor_stack: ResMut<CursorStack>,
+                     mut commands: Commands| {
+                        // otherwise withdrawn once hover cursors are enabled again
+                        if cursor_over_disabled_option.is_none() {
+                            cursor_stack.remove(entity, CursorPriority::HOVERED);
+                        }
+                        if let Ok(mut entity) = commands.get_entity(entity) {
+                            entity.remove::<CursorOver>();
+                        }
+                    },
+                )
+                .on_remove(|world, entity| {
+                    if let Some(mut cursor_stack) = world.get_resource_mut::<CursorStack>() {
+                        cursor_stack.remove(entity, CursorPriority::HOVERED);
                     }
                 })
         })
@@ -660,8 +876,65 @@ pub trait CursorOnHoverable: PointerEventAware {
     }
 }
 
-/// [`Event`] consumed by a global [`Observer`] to set the window's [`CursorIcon`]; the cursor will
-/// be hidden if [`None`].
+/// Priority of a cursor request in the [`CursorStack`]; the request with the highest priority
+/// determines the cursor, with ties going to the most recent request.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct CursorPriority(pub i32);
+
+impl CursorPriority {
+    /// Priority of the base cursor set with [`SetCursor`].
+    pub const DEFAULT: Self = Self(0);
+    /// Priority of cursors requested by hovered [`CursorOnHoverable`] elements.
+    pub const HOVERED: Self = Self(100);
+    /// Priority of cursors requested by dragged [`Draggable`](super::draggable::Draggable)
+    /// elements.
+    pub const DRAG: Self = Self(200);
+}
+
+struct CursorRequest {
+    source: Entity,
+    priority: CursorPriority,
+    icon: Option<CursorIcon>,
+}
+
+/// [`Resource`] holding the cursors requested by elements and other code, each keyed by the
+/// [`Entity`] that requested it and its [`CursorPriority`]. The window the pointer is in shows the
+/// highest priority request, or the default cursor if there are none.
+#[derive(Resource, Default)]
+pub struct CursorStack(Vec<CursorRequest>);
+
+impl CursorStack {
+    /// Request the cursor `icon`, or a hidden cursor if [`None`], on behalf of `source` at
+    /// `priority`, replacing any request `source` previously made at that priority.
+    pub fn push(&mut self, source: Entity, priority: CursorPriority, icon: impl Into<Option<CursorIcon>>) {
+        self.remove(source, priority);
+        self.0.push(CursorRequest {
+            source,
+            priority,
+            icon: icon.into(),
+        });
+    }
+
+    /// Withdraw the request `source` made at `priority`, if any.
+    pub fn remove(&mut self, source: Entity, priority: CursorPriority) {
+        self.0
+            .retain(|request| request.source != source || request.priority != priority);
+    }
+
+    /// The cursor that should be shown, [`None`] if nothing is requested and `Some(None)` if the
+    /// cursor should be hidden.
+    pub fn current(&self) -> Option<Option<&CursorIcon>> {
+        // `max_by_key` returns the last of equal maximums, i.e. the most recent request
+        self.0
+            .iter()
+            .max_by_key(|request| request.priority)
+            .map(|request| request.icon.as_ref())
+    }
+}
+
+/// [`Event`] consumed by a global [`Observer`] to set the base [`CursorIcon`] in the
+/// [`CursorStack`], which is shown when no higher priority cursor is requested; the cursor will be
+/// hidden if [`None`].
 #[derive(Event)]
 pub struct SetCursor(pub Option<CursorIcon>);
 
@@ -669,30 +942,129 @@ pub struct SetCursor(pub Option<CursorIcon>);
 struct CursorOverPropagationStopped;
 
 #[derive(Resource)]
-struct QueuedCursor(Option<CursorIcon>);
+struct QueuedCursor(Entity, Option<CursorIcon>);
 
-fn consume_queued_cursor(queued_cursor: Option<Res<QueuedCursor>>, mut commands: Commands) {
-    if let Some(cursor) = queued_cursor {
-        commands.trigger(SetCursor(cursor.0.clone()));
+fn consume_queued_cursor(
+    queued_cursor: Option<Res<QueuedCursor>>,
+    cursor_overs: Query<(), With<CursorOver>>,
+    mut cursor_stack: ResMut<CursorStack>,
+    mut commands: Commands,
+) {
+    if let Some(queued_cursor) = queued_cursor {
+        let QueuedCursor(entity, cursor) = &*queued_cursor;
+        if cursor_overs.contains(*entity) {
+            cursor_stack.push(*entity, CursorPriority::HOVERED, cursor.clone());
+        }
         commands.remove_resource::<QueuedCursor>();
     }
+    // the pointer left these elements while hover cursors were disabled
+    cursor_stack
+        .0
+        .retain(|request| request.priority != CursorPriority::HOVERED || cursor_overs.contains(request.source));
+}
+
+fn on_set_cursor(event: Trigger<SetCursor>, mut cursor_stack: ResMut<CursorStack>) {
+    let SetCursor(icon_option) = event.event();
+    cursor_stack.push(Entity::PLACEHOLDER, CursorPriority::DEFAULT, icon_option.clone());
+}
+
+// the window the pointer is in, falling back to the primary window
+fn pointer_window(
+    pointer_map: &PointerMap,
+    pointers: &Query<&PointerLocation>,
+    primary_window: &Query<Entity, With<PrimaryWindow>>,
+) -> Option<Entity> {
+    pointer_map
+        .get_entity(PointerId::Mouse)
+        .and_then(|entity| pointers.get(entity).ok())
+        .and_then(|pointer| pointer.location.as_ref())
+        .and_then(|location| match &location.target {
+            NormalizedRenderTarget::Window(window) => Some(window.entity()),
+            _ => None,
+        })
+        .or_else(|| primary_window.single().ok())
+}
+
+// show `icon_option` in the window, hiding the cursor if [`None`]
+fn set_window_cursor(
+    window_entity: Entity,
+    icon_option: Option<&CursorIcon>,
+    windows: &mut Query<(&mut Window, Option<&CursorIcon>)>,
+    commands: &mut Commands,
+) {
+    let Ok((mut window, current_icon)) = windows.get_mut(window_entity) else {
+        return;
+    };
+    if let Some(icon) = icon_option
+        && current_icon != Some(icon)
+        && let Ok(mut window) = commands.get_entity(window_entity)
+    {
+        window.try_insert(icon.clone());
+    }
+    // avoid needlessly triggering change detection
+    if window.cursor_options.visible != icon_option.is_some() {
+        window.cursor_options.visible = icon_option.is_some();
+    }
 }
 
-// TODO: add support for multiple windows
-fn on_set_cursor(
-    event: Trigger<SetCursor>,
-    mut windows: Query<(Entity, &mut Window), With<PrimaryWindow>>,
+#[allow(clippy::too_many_arguments)]
+fn apply_cursor_stack(
+    cursor_stack: Res<CursorStack>,
+    pointer_map: Res<PointerMap>,
+    pointers: Query<&PointerLocation>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    mut windows: Query<(&mut Window, Option<&CursorIcon>)>,
+    mut last_window: Local<Option<Entity>>,
+    // whether a cursor was requested when the stack was last applied
+    mut requested: Local<bool>,
     mut commands: Commands,
 ) {
-    if let Ok((entity, mut window)) = windows.single_mut() {
-        let SetCursor(icon_option) = event.event();
-        if let Some(icon) = icon_option.clone() {
-            if let Ok(mut window) = commands.get_entity(entity) {
-                window.try_insert(icon);
+    let Some(window_entity) = pointer_window(&pointer_map, &pointers, &primary_window) else {
+        return;
+    };
+    let window_changed = *last_window != Some(window_entity);
+    if !cursor_stack.is_changed() && !window_changed {
+        return;
+    }
+    let current = cursor_stack.current();
+    let was_requested = mem::replace(&mut *requested, current.is_some());
+    // the window the pointer left keeps the requested cursor otherwise
+    if window_changed
+        && was_requested
+        && let Some(last_window) = *last_window
+    {
+        set_window_cursor(last_window, Some(&CursorIcon::default()), &mut windows, &mut commands);
+    }
+    *last_window = Some(window_entity);
+    // only touch the cursor while one is requested or right after the last request is withdrawn,
+    // so cursors set by other means aren't clobbered
+    match current {
+        Some(icon_option) => set_window_cursor(window_entity, icon_option, &mut windows, &mut commands),
+        None if was_requested && !window_changed => {
+            set_window_cursor(window_entity, Some(&CursorIcon::default()), &mut windows, &mut commands)
+        }
+        None => (),
+    }
+}
+
+// marks the root of a tree of entities which neither block picking nor are hovered, like drag
+// ghosts and tooltips
+#[derive(Component)]
+pub(crate) struct PickingIgnoredTree;
+
+// keeps every entity of the trees `Pickable::IGNORE`, including descendants spawned, or inserting
+// their own `Pickable`, after the root
+pub(crate) fn ignore_tree_picking(
+    roots: Query<Entity, With<PickingIgnoredTree>>,
+    childrens: Query<&Children>,
+    pickables: Query<Option<&Pickable>>,
+    mut commands: Commands,
+) {
+    for root in roots.iter() {
+        for entity in iter::once(root).chain(childrens.iter_descendants(root)) {
+            if !matches!(pickables.get(entity), Ok(Some(Pickable { should_block_lower: false, is_hoverable: false }))) {
+                commands.entity(entity).try_insert(Pickable::IGNORE);
             }
-            window.cursor_options.visible = true;
-        } else {
-            window.cursor_options.visible = false;
         }
     }
 }
@@ -703,18 +1075,45 @@ fn on_set_cursor(
 pub struct UpdateHoverStatesDisabled;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_observer(on_set_cursor).add_systems(
-        Update,
-        (
-            pressable_system.run_if(any_with_component::<Pressable>),
-            update_hover_states.run_if(
-                any_with_component::<Hovered>
-                    // TODO: apparently this updates every frame no matter what, if so, remove this condition
-                    // TODO: remove when native `Enter` and `Leave` available
-                    .and(resource_exists_and_changed::<HoverMap>)
-                    .and(not(resource_exists::<UpdateHoverStatesDisabled>)),
+    app.init_resource::<CursorStack>()
+        .add_observer(on_set_cursor)
+        .add_systems(
+            PreUpdate,
+            ignore_tree_picking
+                .before(PickSet::Backend)
+                .run_if(any_with_component::<PickingIgnoredTree>),
+        )
+        .add_systems(
+            Update,
+            (
+                pressable_system.run_if(any_with_component::<Pressable>),
+                update_hover_states.run_if(
+                    any_with_component::<Hovered>
+                        // TODO: apparently this updates every frame no matter what, if so, remove this condition
+                        // TODO: remove when native `Enter` and `Leave` available
+                        .and(resource_exists_and_changed::<HoverMap>)
+                        .and(not(resource_exists::<UpdateHoverStatesDisabled>)),
+                ),
+                consume_queued_cursor.run_if(resource_removed::<CursorOnHoverDisabled>),
+                apply_cursor_stack,
             ),
-            consume_queued_cursor.run_if(resource_removed::<CursorOnHoverDisabled>),
-        ),
-    );
+        );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[test]
+    fn hover_cursors_left_while_disabled_are_withdrawn_once_enabled() {
+        let mut world = World::new();
+        let (left, hovered) = (world.spawn_empty().id(), world.spawn(CursorOver).id());
+        let mut cursor_stack = CursorStack::default();
+        cursor_stack.push(hovered, CursorPriority::HOVERED, CursorIcon::default());
+        cursor_stack.push(left, CursorPriority::HOVERED, None);
+        world.insert_resource(cursor_stack);
+        world.run_system_once(consume_queued_cursor).unwrap();
+        assert_eq!(world.resource::<CursorStack>().current(), Some(Some(&CursorIcon::default())));
+    }
 }
@@ -3,17 +3,19 @@ use bevy_picking::prelude::*;
 use bevy_ui::prelude::*;
 use futures_signals::{
     signal::{Signal, SignalExt},
-    signal_vec::{SignalVec, SignalVecExt},
+    signal_vec::{MutableVec, SignalVec, SignalVecExt},
 };
 
 use super::{
     align::{AddRemove, AlignHolder, Alignable, Aligner, Alignment, ChildAlignable},
+    draggable::{Draggable, DropTarget, reorderable_items},
     element::{IntoOptionElement, Nameable, UiRootable},
     global_event_aware::GlobalEventAware,
     mouse_wheel_scrollable::MouseWheelScrollable,
     pointer_event_aware::{CursorOnHoverable, PointerEventAware},
     raw::{RawElWrapper, RawHaalkaEl},
-    viewport_mutable::ViewportMutable,
+    tooltip::Tooltipable,
+    viewport_mutable::{Axis, ScrollIntoViewable, Stickable, ViewportMutable},
 };
 
 /// [`Element`](super::element::Element) with horizontally stacked children. Port of [MoonZoon](https://github.com/MoonZoon/MoonZoon)'s [`Row`](https://github.com/MoonZoon/MoonZoon/blob/main/crates/zoon/src/element/row.rs).
@@ -63,11 +65,16 @@ impl<NodeType: Bundle> RawElWrapper for Row<NodeType> {
 }
 
 impl<NodeType: Bundle> CursorOnHoverable for Row<NodeType> {}
+impl<NodeType: Bundle> Draggable for Row<NodeType> {}
+impl<NodeType: Bundle> DropTarget for Row<NodeType> {}
 impl<NodeType: Bundle> GlobalEventAware for Row<NodeType> {}
 impl<NodeType: Bundle> Nameable for Row<NodeType> {}
 impl<NodeType: Bundle> PointerEventAware for Row<NodeType> {}
 impl<NodeType: Bundle> MouseWheelScrollable for Row<NodeType> {}
 impl<NodeType: Bundle> UiRootable for Row<NodeType> {}
+impl<NodeType: Bundle> Tooltipable for Row<NodeType> {}
+impl<NodeType: Bundle> ScrollIntoViewable for Row<NodeType> {}
+impl<NodeType: Bundle> Stickable for Row<NodeType> {}
 impl<NodeType: Bundle> ViewportMutable for Row<NodeType> {}
 
 impl<NodeType: Bundle> Row<NodeType> {
@@ -136,6 +143,19 @@ impl<NodeType: Bundle> Row<NodeType> {
         self
     }
 
+    /// Declare reactive horizontally stacked children from a [`MutableVec`], which can be
+    /// reordered by dragging them with the primary pointer button. While an item is dragged, the
+    /// other items shift to leave a gap where it would be dropped; dropping it moves it to that
+    /// index of the [`MutableVec`]. Items are [`Draggable`] with their value as the payload, so
+    /// they can also be dropped onto [`DropTarget`]s.
+    pub fn items_signal_vec_reorderable<T: Clone + Send + Sync + 'static, IOE: IntoOptionElement + 'static>(
+        self,
+        items: MutableVec<T>,
+        item: impl FnMut(T) -> IOE + Send + 'static,
+    ) -> Self {
+        self.items_signal_vec(reorderable_items(items, Axis::Horizontal, item))
+    }
+
     /// When the width of the row exceeds the width of its parent, wrap the row's children to the
     /// next line, recursively.
     pub fn multiline(mut self) -> Self {
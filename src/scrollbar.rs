@@ -0,0 +1,427 @@
+//! Scrollbar widget which displays and controls the [`Viewport`](super::viewport_mutable::Viewport)
+//! of a [`ViewportMutable`](super::viewport_mutable::ViewportMutable) element, see [`Scrollbar`].
+
+use std::mem;
+
+use bevy_app::prelude::*;
+use bevy_color::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_math::prelude::*;
+use bevy_picking::prelude::*;
+use bevy_render::prelude::*;
+use bevy_ui::{UiSystem, prelude::*};
+use futures_signals::signal::{Signal, SignalExt, always};
+
+use crate::impl_haalka_methods;
+
+use super::{
+    el::El,
+    element::{ElementWrapper, Nameable, UiRootable},
+    global_event_aware::GlobalEventAware,
+    mouse_wheel_scrollable::{ScrollBy, ScrollMode, max_scroll_offset},
+    pointer_event_aware::{CursorOnHoverable, ElementSpace, PointerEventAware},
+    raw::RawElWrapper,
+};
+use paste::paste;
+
+// the shortest a thumb gets, in logical pixels, so it remains grabbable for long contents
+const MIN_THUMB_LENGTH: f32 = 16.;
+// scrollable ranges, in logical pixels, at or below this are considered to fit their viewport
+const FIT_EPSILON: f32 = 0.5;
+
+/// Points from a scrollbar's track to the [`ViewportMutable`](super::viewport_mutable::ViewportMutable)
+/// element it controls.
+#[derive(Component, Clone, Copy)]
+struct ScrollbarOf(Entity);
+
+#[derive(Component, Clone, Copy)]
+struct ScrollbarTrack {
+    horizontal: bool,
+    auto_hide: bool,
+    // whether auto hiding last hid the track, so its `Visibility` is only written when that changes
+    // and can otherwise be set freely
+    auto_hidden: Option<bool>,
+    // how the viewport moves when the track is clicked
+    mode: ScrollMode,
+}
+
+#[derive(Component)]
+struct ScrollbarThumb;
+
+/// Horizontal or vertical scrollbar whose thumb reflects the size and position of a
+/// [`ViewportMutable`](super::viewport_mutable::ViewportMutable) element's viewport relative to its
+/// [`Scene`](super::viewport_mutable::Scene). Dragging the thumb scrolls the viewport and clicking
+/// the track pages it towards the click. The scrollbar is bound to its viewport by [`Entity`], e.g.
+/// one synced with
+/// [`.viewport_entity_sync`](super::viewport_mutable::ViewportMutable::viewport_entity_sync).
+///
+/// The scrollbar itself is the track, styled like any other element, while the thumb is styled via
+/// [`.thumb`](Scrollbar::thumb); both come with minimal defaults.
+pub struct Scrollbar {
+    el: El<Node>,
+    thumb: El<Node>,
+}
+
+impl ElementWrapper for Scrollbar {
+    type EL = El<Node>;
+    fn element_mut(&mut self) -> &mut Self::EL {
+        &mut self.el
+    }
+
+    fn into_el(self) -> Self::EL {
+        let Self { el, thumb } = self;
+        el.child(thumb)
+    }
+}
+
+impl CursorOnHoverable for Scrollbar {}
+impl GlobalEventAware for Scrollbar {}
+impl Nameable for Scrollbar {}
+impl PointerEventAware for Scrollbar {}
+impl UiRootable for Scrollbar {}
+
+impl Scrollbar {
+    fn new(horizontal: bool) -> Self {
+        let el = El::<Node>::new()
+            .with_node(move |mut node| {
+                if horizontal {
+                    node.width = Val::Percent(100.);
+                    node.height = Val::Px(8.);
+                } else {
+                    node.width = Val::Px(8.);
+                    node.height = Val::Percent(100.);
+                }
+            })
+            .background_color(BackgroundColor(Color::srgba(0., 0., 0., 0.2)))
+            .update_raw_el(move |raw_el| {
+                raw_el
+                    .insert((
+                        ScrollbarTrack {
+                            horizontal,
+                            auto_hide: true,
+                            auto_hidden: None,
+                            mode: ScrollMode::default(),
+                        },
+                        Pickable::default(),
+                    ))
+                    .observe(page_on_track_press)
+            });
+        let thumb = El::<Node>::new()
+            .with_node(move |mut node| {
+                node.position_type = PositionType::Absolute;
+                if horizontal {
+                    node.height = Val::Percent(100.);
+                } else {
+                    node.width = Val::Percent(100.);
+                }
+            })
+            .background_color(BackgroundColor(Color::srgba(1., 1., 1., 0.5)))
+            .border_radius(BorderRadius::MAX)
+            .update_raw_el(|raw_el| {
+                raw_el
+                    .insert((ScrollbarThumb, Pickable::default()))
+                    .observe(scroll_on_thumb_drag)
+            });
+        Self { el, thumb }
+    }
+
+    /// Construct a scrollbar for the horizontal axis.
+    pub fn horizontal() -> Self {
+        Self::new(true)
+    }
+
+    /// Construct a scrollbar for the vertical axis.
+    pub fn vertical() -> Self {
+        Self::new(false)
+    }
+
+    /// Bind this scrollbar to the viewport of the
+    /// [`ViewportMutable`](super::viewport_mutable::ViewportMutable) element `viewport`.
+    pub fn viewport(self, viewport_option: impl Into<Option<Entity>>) -> Self {
+        self.viewport_signal(always(viewport_option.into()))
+    }
+
+    /// Reactively bind this scrollbar to the viewport of a
+    /// [`ViewportMutable`](super::viewport_mutable::ViewportMutable) element. When the [`Signal`]
+    /// outputs [`None`], the scrollbar is unbound.
+    pub fn viewport_signal<S: Signal<Item = impl Into<Option<Entity>>> + Send + 'static>(
+        mut self,
+        viewport_option_signal_option: impl Into<Option<S>>,
+    ) -> Self {
+        if let Some(viewport_option_signal) = viewport_option_signal_option.into() {
+            self = self.update_raw_el(|raw_el| {
+                raw_el.component_signal::<ScrollbarOf, _>(
+                    viewport_option_signal.map(|viewport_option| viewport_option.into().map(ScrollbarOf)),
+                )
+            });
+        }
+        self
+    }
+
+    /// Set whether this scrollbar is hidden while its viewport's contents fit within it, `true` by
+    /// default.
+    pub fn auto_hide(self, auto_hide: bool) -> Self {
+        self.update_raw_el(move |raw_el| {
+            raw_el.with_component::<ScrollbarTrack>(move |mut track| {
+                track.auto_hide = auto_hide;
+                track.auto_hidden = None;
+            })
+        })
+    }
+
+    /// Set how the viewport moves when the track is clicked, see [`ScrollMode`].
+    pub fn mode(self, mode: ScrollMode) -> Self {
+        self.update_raw_el(move |raw_el| raw_el.with_component::<ScrollbarTrack>(move |mut track| track.mode = mode))
+    }
+
+    /// Style this scrollbar's thumb; its offset and length along the scrollbar's axis are managed by
+    /// the scrollbar.
+    pub fn thumb(mut self, f: impl FnOnce(El<Node>) -> El<Node>) -> Self {
+        self.thumb = f(mem::take(&mut self.thumb));
+        self
+    }
+}
+
+impl_haalka_methods! {
+    Scrollbar {
+        node: Node,
+        background_color: BackgroundColor,
+        border_color: BorderColor,
+        border_radius: BorderRadius,
+        visibility: Visibility,
+    }
+}
+
+fn along(horizontal: bool, v: Vec2) -> f32 {
+    if horizontal { v.x } else { v.y }
+}
+
+fn axis(horizontal: bool, length: f32) -> Vec2 {
+    if horizontal {
+        Vec2::new(length, 0.)
+    } else {
+        Vec2::new(0., length)
+    }
+}
+
+// the length of the track and the viewport's scrollable range, in logical pixels, along the
+// track's axis
+fn track_metrics(track_node: &ComputedNode, viewport_node: &ComputedNode, horizontal: bool) -> (f32, f32) {
+    (
+        along(horizontal, track_node.size()) * track_node.inverse_scale_factor(),
+        along(horizontal, max_scroll_offset(viewport_node)),
+    )
+}
+
+fn scroll_on_thumb_drag(
+    mut drag: Trigger<Pointer<Drag>>,
+    child_ofs: Query<&ChildOf>,
+    tracks: Query<(&ScrollbarTrack, &ScrollbarOf, &ComputedNode)>,
+    computed_nodes: Query<&ComputedNode>,
+    ui_scale: Res<UiScale>,
+    mut commands: Commands,
+) {
+    if !matches!(drag.button, PointerButton::Primary) {
+        return;
+    }
+    drag.propagate(false);
+    let thumb = drag.target();
+    if let Ok(&ChildOf(track)) = child_ofs.get(thumb)
+        && let Ok((&ScrollbarTrack { horizontal, .. }, &ScrollbarOf(viewport), track_node)) = tracks.get(track)
+        && let Ok(viewport_node) = computed_nodes.get(viewport)
+        && let Ok(thumb_node) = computed_nodes.get(thumb)
+    {
+        let (track_length, max) = track_metrics(track_node, viewport_node, horizontal);
+        let thumb_length = along(horizontal, thumb_node.size()) * thumb_node.inverse_scale_factor();
+        let travel = track_length - thumb_length;
+        if travel <= 0. {
+            return;
+        }
+        // pointer deltas are in logical window pixels, which do not account for `UiScale`
+        let delta = along(horizontal, drag.delta) / ui_scale.0 * max / travel;
+        commands.trigger_targets(
+            ScrollBy {
+                delta: axis(horizontal, delta),
+                mode: ScrollMode::Instant,
+                held: true,
+            },
+            viewport,
+        );
+    }
+}
+
+fn page_on_track_press(
+    press: Trigger<Pointer<Pressed>>,
+    tracks: Query<(&ScrollbarTrack, &ScrollbarOf, &Children)>,
+    thumbs: Query<(), With<ScrollbarThumb>>,
+    computed_nodes: Query<&ComputedNode>,
+    element_space: ElementSpace,
+    ui_scale: Res<UiScale>,
+    mut commands: Commands,
+) {
+    if !matches!(press.button, PointerButton::Primary) {
+        return;
+    }
+    let track = press.target();
+    let Ok((&ScrollbarTrack { horizontal, mode, .. }, &ScrollbarOf(viewport), children)) = tracks.get(track) else {
+        return;
+    };
+    if let Some(thumb) = children.iter().find(|&child| thumbs.contains(child))
+        && let Some(thumb_rect) = element_space.rect(thumb)
+        && let Ok(viewport_node) = computed_nodes.get(viewport)
+    {
+        let position = along(horizontal, press.pointer_location.position / ui_scale.0);
+        // presses on the thumb itself start drags instead
+        let direction = if position < along(horizontal, thumb_rect.min) {
+            -1.
+        } else if position > along(horizontal, thumb_rect.max) {
+            1.
+        } else {
+            return;
+        };
+        let page = along(horizontal, viewport_node.size()) * viewport_node.inverse_scale_factor();
+        commands.trigger_targets(
+            ScrollBy {
+                delta: axis(horizontal, direction * page),
+                mode,
+                held: false,
+            },
+            viewport,
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn update_scrollbars(
+    mut tracks: Query<
+        (
+            &mut ScrollbarTrack,
+            Option<&ScrollbarOf>,
+            &ComputedNode,
+            &mut Visibility,
+        ),
+        Without<ScrollbarThumb>,
+    >,
+    viewports: Query<(&ComputedNode, &ScrollPosition)>,
+    mut thumbs: Query<(&ChildOf, &mut Node), With<ScrollbarThumb>>,
+) {
+    for (&ChildOf(track), mut thumb_node) in thumbs.iter_mut() {
+        let Ok((mut track, scrollbar_of, track_node, mut visibility)) = tracks.get_mut(track) else {
+            continue;
+        };
+        let horizontal = track.horizontal;
+        let mut auto_hide = |hidden: bool| {
+            if track.auto_hide && track.auto_hidden != Some(hidden) {
+                track.auto_hidden = Some(hidden);
+                *visibility = if hidden {
+                    Visibility::Hidden
+                } else {
+                    Visibility::Inherited
+                };
+            }
+        };
+        let Some((viewport_node, scroll_position)) =
+            scrollbar_of.and_then(|&ScrollbarOf(viewport)| viewports.get(viewport).ok())
+        else {
+            auto_hide(true);
+            continue;
+        };
+        let (track_length, max) = track_metrics(track_node, viewport_node, horizontal);
+        auto_hide(max <= FIT_EPSILON);
+        let content = along(horizontal, viewport_node.content_size());
+        let visible_fraction = if content > 0. {
+            (along(horizontal, viewport_node.size()) / content).min(1.)
+        } else {
+            1.
+        };
+        let thumb_length = (track_length * visible_fraction)
+            .max(MIN_THUMB_LENGTH)
+            .min(track_length);
+        let position = if horizontal {
+            scroll_position.offset_x
+        } else {
+            scroll_position.offset_y
+        };
+        let progress = if max > FIT_EPSILON {
+            (position / max).clamp(0., 1.)
+        } else {
+            0.
+        };
+        let offset = Val::Px(progress * (track_length - thumb_length));
+        let length = Val::Px(thumb_length);
+        let (current_offset, current_length) = if horizontal {
+            (thumb_node.left, thumb_node.width)
+        } else {
+            (thumb_node.top, thumb_node.height)
+        };
+        // avoid triggering relayouts when nothing moved
+        if current_offset != offset || current_length != length {
+            if horizontal {
+                thumb_node.left = offset;
+                thumb_node.width = length;
+            } else {
+                thumb_node.top = offset;
+                thumb_node.height = length;
+            }
+        }
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        PostUpdate,
+        update_scrollbars
+            .before(UiSystem::Layout)
+            .run_if(any_with_component::<ScrollbarThumb>),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_utils::prelude::*;
+
+    #[test]
+    fn auto_hide_only_writes_visibility_when_it_changes() {
+        let mut world = World::new();
+        let viewport = world
+            .spawn((
+                ComputedNode {
+                    size: Vec2::splat(100.),
+                    content_size: Vec2::splat(100.),
+                    inverse_scale_factor: 1.,
+                    ..default()
+                },
+                ScrollPosition::default(),
+            ))
+            .id();
+        let track = world
+            .spawn((
+                ScrollbarTrack {
+                    horizontal: false,
+                    auto_hide: true,
+                    auto_hidden: None,
+                    mode: ScrollMode::default(),
+                },
+                ScrollbarOf(viewport),
+                ComputedNode {
+                    size: Vec2::new(10., 100.),
+                    inverse_scale_factor: 1.,
+                    ..default()
+                },
+                Visibility::Inherited,
+            ))
+            .id();
+        world.spawn((ScrollbarThumb, Node::default(), ChildOf(track)));
+        world.run_system_once(update_scrollbars).unwrap();
+        assert_eq!(world.get::<Visibility>(track), Some(&Visibility::Hidden));
+        // visibility set while the contents still fit is left be
+        *world.get_mut::<Visibility>(track).unwrap() = Visibility::Visible;
+        world.run_system_once(update_scrollbars).unwrap();
+        assert_eq!(world.get::<Visibility>(track), Some(&Visibility::Visible));
+        world.get_mut::<ComputedNode>(viewport).unwrap().content_size = Vec2::new(100., 400.);
+        world.run_system_once(update_scrollbars).unwrap();
+        assert_eq!(world.get::<Visibility>(track), Some(&Visibility::Inherited));
+    }
+}
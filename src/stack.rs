@@ -8,13 +8,15 @@ use futures_signals::{
 
 use super::{
     align::{AddRemove, AlignHolder, Alignable, Aligner, Alignment, ChildAlignable},
+    draggable::{Draggable, DropTarget},
     element::{IntoOptionElement, Nameable, UiRootable},
     global_event_aware::GlobalEventAware,
     mouse_wheel_scrollable::MouseWheelScrollable,
     pointer_event_aware::{CursorOnHoverable, PointerEventAware},
     raw::{RawElWrapper, RawHaalkaEl},
     row::Row,
-    viewport_mutable::ViewportMutable,
+    tooltip::Tooltipable,
+    viewport_mutable::{ScrollIntoViewable, Stickable, ViewportMutable},
 };
 
 /// [`Element`](super::element::Element) with children stacked on directly on top of each other (e.g. along the z-axis), with siblings ordered youngest to oldest, top to bottom. Port of [MoonZoon](https://github.com/MoonZoon/MoonZoon)'s [`Stack`](https://github.com/MoonZoon/MoonZoon/blob/main/crates/zoon/src/element/stack.rs).
@@ -66,11 +68,16 @@ impl<NodeType: Bundle> RawElWrapper for Stack<NodeType> {
 }
 
 impl<NodeType: Bundle> CursorOnHoverable for Stack<NodeType> {}
+impl<NodeType: Bundle> Draggable for Stack<NodeType> {}
+impl<NodeType: Bundle> DropTarget for Stack<NodeType> {}
 impl<NodeType: Bundle> GlobalEventAware for Stack<NodeType> {}
 impl<NodeType: Bundle> Nameable for Stack<NodeType> {}
 impl<NodeType: Bundle> PointerEventAware for Stack<NodeType> {}
 impl<NodeType: Bundle> MouseWheelScrollable for Stack<NodeType> {}
 impl<NodeType: Bundle> UiRootable for Stack<NodeType> {}
+impl<NodeType: Bundle> Tooltipable for Stack<NodeType> {}
+impl<NodeType: Bundle> ScrollIntoViewable for Stack<NodeType> {}
+impl<NodeType: Bundle> Stickable for Stack<NodeType> {}
 impl<NodeType: Bundle> ViewportMutable for Stack<NodeType> {}
 
 impl<NodeType: Bundle> Stack<NodeType> {
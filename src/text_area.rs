@@ -0,0 +1,223 @@
+//! Multiline text input which grows with its contents and then scrolls, see [`TextArea`].
+
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_math::prelude::*;
+use bevy_text::{
+    LineBreak,
+    cosmic_text::{Buffer, Cursor, Edit, Wrap},
+};
+use bevy_ui::prelude::*;
+use bevy_ui_text_input::{TextInputBuffer, TextInputMode};
+
+use super::{
+    el::El,
+    element::{ElementWrapper, Nameable, UiRootable},
+    global_event_aware::GlobalEventAware,
+    mouse_wheel_scrollable::{
+        BasicScrollHandler, MouseWheelScrollable, OnHoverMouseWheelScrollable, ScrollDirection, ScrollMode,
+    },
+    pointer_event_aware::{CursorOnHoverable, PointerEventAware},
+    raw::RawElWrapper,
+    text_input::TextInput,
+    viewport_mutable::{Axis, ViewportCommands, ViewportMutable},
+};
+
+/// Multiline text input which grows with its contents from [`.min_rows`](TextArea::min_rows) up
+/// to [`.max_rows`](TextArea::max_rows) lines, after which its viewport scrolls, keeping the caret
+/// in view while typing.
+///
+/// The [`TextArea`] itself is the scrolling viewport, scrolled by the mouse wheel while hovered by
+/// default; the [`TextInput`] inside it can be configured with
+/// [`.text_input`](TextArea::text_input).
+pub struct TextArea {
+    el: El<Node>,
+    text_input: TextInput,
+}
+
+impl ElementWrapper for TextArea {
+    type EL = El<Node>;
+    fn element_mut(&mut self) -> &mut Self::EL {
+        &mut self.el
+    }
+
+    fn into_el(self) -> Self::EL {
+        let Self { el, text_input } = self;
+        el.child(text_input)
+    }
+}
+
+impl GlobalEventAware for TextArea {}
+impl Nameable for TextArea {}
+impl PointerEventAware for TextArea {}
+impl MouseWheelScrollable for TextArea {}
+impl UiRootable for TextArea {}
+impl ViewportMutable for TextArea {}
+impl CursorOnHoverable for TextArea {}
+
+impl Default for TextArea {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextArea {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {
+            el: El::<Node>::new()
+                .with_node(|mut node| node.flex_direction = FlexDirection::Column)
+                .mutable_viewport(Axis::Vertical)
+                .on_scroll_with_system_on_hover(
+                    BasicScrollHandler::new()
+                        .direction(ScrollDirection::Vertical)
+                        .pixels(20.)
+                        .into_system(),
+                ),
+            text_input: TextInput::new()
+                .with_node(|mut node| {
+                    node.width = Val::Percent(100.);
+                    node.flex_shrink = 0.;
+                })
+                .with_text_input_node(|mut text_input_node| {
+                    text_input_node.mode = TextInputMode::MultiLine {
+                        wrap: Wrap::WordOrGlyph,
+                    }
+                })
+                .update_raw_el(|raw_el| raw_el.insert(Rows::default())),
+        }
+    }
+
+    /// Set how lines wrap when they are wider than the area, [`LineBreak::WordOrCharacter`] by
+    /// default; [`LineBreak::NoWrap`] leaves long lines clipped.
+    pub fn wrap(mut self, line_break: LineBreak) -> Self {
+        let wrap = match line_break {
+            LineBreak::WordBoundary => Wrap::Word,
+            LineBreak::AnyCharacter => Wrap::Glyph,
+            LineBreak::WordOrCharacter => Wrap::WordOrGlyph,
+            LineBreak::NoWrap => Wrap::None,
+        };
+        self.text_input = self
+            .text_input
+            .with_text_input_node(move |mut text_input_node| text_input_node.mode = TextInputMode::MultiLine { wrap });
+        self
+    }
+
+    /// Set the number of lines the area is tall when it has fewer lines of text, `1` by default.
+    pub fn min_rows(self, min_rows: usize) -> Self {
+        self.with_rows(move |rows| rows.min = min_rows.max(1))
+    }
+
+    /// Set the number of lines the area grows to before it starts scrolling; [`None`], the
+    /// default, grows without limit.
+    pub fn max_rows(self, max_rows_option: impl Into<Option<usize>>) -> Self {
+        let max_rows_option = max_rows_option.into();
+        self.with_rows(move |rows| rows.max = max_rows_option)
+    }
+
+    /// Modify the [`TextInput`], e.g. to set its font or listen to its changes.
+    pub fn text_input(mut self, f: impl FnOnce(TextInput) -> TextInput) -> Self {
+        self.text_input = f(self.text_input);
+        self
+    }
+
+    fn with_rows(mut self, f: impl FnOnce(&mut Rows) + Send + 'static) -> Self {
+        self.text_input = self
+            .text_input
+            .update_raw_el(|raw_el| raw_el.with_component::<Rows>(move |mut rows| f(&mut rows)));
+        self
+    }
+}
+
+#[derive(Component)]
+struct Rows {
+    min: usize,
+    max: Option<usize>,
+    // the caret the viewport was last scrolled to, so scrolling away from an idle caret sticks
+    cursor: Option<Cursor>,
+}
+
+impl Default for Rows {
+    fn default() -> Self {
+        Self {
+            min: 1,
+            max: None,
+            cursor: None,
+        }
+    }
+}
+
+// the number of visual lines in `buffer`, counting wrapped lines, and the visual line containing `cursor`
+fn visual_lines(buffer: &Buffer, cursor: Cursor) -> (usize, usize) {
+    let mut lines = 0;
+    let mut cursor_line = 0;
+    for (i, line) in buffer.lines.iter().enumerate() {
+        let layout = line.layout_opt().map(Vec::as_slice).unwrap_or_default();
+        if i == cursor.line {
+            cursor_line = lines
+                + layout
+                    .iter()
+                    .position(|layout_line| layout_line.glyphs.last().is_some_and(|glyph| cursor.index <= glyph.end))
+                    .unwrap_or(layout.len().saturating_sub(1));
+        }
+        lines += layout.len().max(1);
+    }
+    (lines, cursor_line)
+}
+
+#[allow(clippy::type_complexity)]
+fn size_text_areas(
+    mut inputs: Query<(&TextInputBuffer, &ComputedNode, &mut Node, &mut Rows, &ChildOf)>,
+    mut viewports: Query<(&ComputedNode, &ScrollPosition, &mut Node), Without<Rows>>,
+    mut commands: Commands,
+) {
+    for (buffer, computed_node, mut node, mut rows, child_of) in inputs.iter_mut() {
+        let cursor = buffer.editor.cursor();
+        let (lines, line_height, cursor_line) = buffer.editor.with_buffer(|buffer| {
+            let (lines, cursor_line) = visual_lines(buffer, cursor);
+            (lines, buffer.metrics().line_height, cursor_line)
+        });
+        // the buffer is laid out in physical pixels while nodes are sized in logical pixels
+        let line_height = line_height * computed_node.inverse_scale_factor();
+        if line_height <= 0. {
+            continue;
+        }
+        let height = Val::Px(lines.max(rows.min) as f32 * line_height);
+        if node.height != height {
+            node.height = height;
+        }
+        let Ok((viewport_computed_node, scroll_position, mut viewport_node)) = viewports.get_mut(child_of.parent())
+        else {
+            continue;
+        };
+        let (padding, border) = (viewport_computed_node.padding(), viewport_computed_node.border());
+        let inset =
+            (padding.top + padding.bottom + border.top + border.bottom) * viewport_computed_node.inverse_scale_factor();
+        let max_height = rows
+            .max
+            .map_or(Val::Auto, |max| Val::Px(max.max(rows.min) as f32 * line_height + inset));
+        if viewport_node.max_height != max_height {
+            viewport_node.max_height = max_height;
+        }
+        if rows.cursor == Some(cursor) {
+            continue;
+        }
+        rows.cursor = Some(cursor);
+        let visible = viewport_computed_node.size().y * viewport_computed_node.inverse_scale_factor() - inset;
+        let (top, bottom) = (cursor_line as f32 * line_height, (cursor_line + 1) as f32 * line_height);
+        let offset = if top < scroll_position.offset_y {
+            top
+        } else if bottom > scroll_position.offset_y + visible {
+            bottom - visible
+        } else {
+            continue;
+        };
+        commands
+            .entity(child_of.parent())
+            .scroll_to(Vec2::new(scroll_position.offset_x, offset), ScrollMode::Instant);
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, size_text_areas.run_if(any_with_component::<Rows>));
+}
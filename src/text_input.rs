@@ -1,32 +1,41 @@
 //! Reactive text input widget and adjacent utilities, a thin wrapper around [`bevy_ui_text_input`] integrated with [`Signal`]s.
 
-use std::{ops::Not, sync::{Arc, OnceLock}};
+use std::{collections::VecDeque, mem, ops::{Not, Range}, sync::{Arc, OnceLock}, time::Duration};
 
-use bevy_input_focus::InputFocus;
+use bevy_input::{keyboard::{Key, KeyboardInput}, prelude::*};
+use bevy_input_focus::{FocusedInput, InputFocus};
+use bevy_color::prelude::*;
 use bevy_ecs::system::*;
 use bevy_ecs::prelude::*;
 use bevy_ui::prelude::*;
 use bevy_utils::prelude::*;
 use bevy_app::prelude::*;
 use bevy_picking::prelude::*;
-use bevy_text::{cosmic_text::{Edit, Selection}, TextColor, TextFont};
+use bevy_time::prelude::*;
+use bevy_math::curve::{Curve, EaseFunction};
+use bevy_text::{cosmic_text::{Buffer, Cursor, Edit, Selection}, TextColor, TextFont};
 
 use crate::impl_haalka_methods;
 
 use super::{
     el::El, element::{ElementWrapper, Nameable, UiRootable}, pointer_event_aware::{PointerEventAware, CursorOnHoverable}, raw::{RawElWrapper, register_system}, mouse_wheel_scrollable::MouseWheelScrollable,
-    utils::clone, viewport_mutable::ViewportMutable, global_event_aware::GlobalEventAware,
+    utils::clone, viewport_mutable::ViewportMutable, global_event_aware::GlobalEventAware, clipboard::TextInputClipboard,
     raw::{observe, utils::remove_system_holder_on_remove}
 };
 use apply::Apply;
 use bevy_ui_text_input::{actions::TextInputAction, text_input_pipeline::TextInputPipeline, *};
-use futures_signals::signal::{Mutable, Signal, SignalExt};
+use futures_signals::signal::{Mutable, Signal, SignalExt, always};
 use paste::paste;
 
 /// Reactive text input widget, a thin wrapper around [`bevy_ui_text_input`] integrated with [`Signal`]s.
 #[derive(Default)]
 pub struct TextInput {
     el: El<Node>,
+    error: Mutable<Option<String>>,
+    caret: Mutable<usize>,
+    selection: Mutable<Option<Range<usize>>>,
+    can_undo: Mutable<bool>,
+    can_redo: Mutable<bool>,
 }
 
 impl ElementWrapper for TextInput {
@@ -48,6 +57,9 @@ impl CursorOnHoverable for TextInput {}
 impl TextInput {
     #[allow(missing_docs, clippy::new_without_default)]
     pub fn new() -> Self {
+        let caret = Mutable::new(0);
+        let selection = Mutable::new(None);
+        let (can_undo, can_redo) = (Mutable::new(false), Mutable::new(false));
         let el = El::<Node>::new().update_raw_el(|raw_el| {
             raw_el
                 .insert((
@@ -56,10 +68,14 @@ impl TextInput {
                         ..default()
                     },
                     Pickable::default(),
-                    LastSignalText::default()
+                    LastSignalText::default(),
+                    SelectionState { caret: caret.clone(), selection: selection.clone() },
+                    TextInputContents::default(),
+                    History::new(can_undo.clone(), can_redo.clone()),
+                    DeferredActions::default(),
                 ))
         });
-        Self { el }
+        Self { el, error: Mutable::new(None), caret, selection, can_undo, can_redo }
     }
 
     /// Run a function with this input's [`TextInputBuffer`] with access to [`ResMut<TextInputPipeline>`].
@@ -102,15 +118,21 @@ impl TextInput {
         })
     }
 
-    /// Set the text of this input.
+    /// Set the text of this input, resetting its undo history.
     pub fn text(self, text_option: impl Into<Option<String>>) -> Self {
         let text = text_option.into().unwrap_or_default();
-        self.with_text_input_queue(move |mut text_input_queue| {
-            queue_set_text_actions(&mut text_input_queue, text);
+        self.update_raw_el(|raw_el| {
+            raw_el.with_entity(move |mut entity| {
+                if let Some(mut history) = entity.get_mut::<History>() {
+                    history.pending = Some((text.clone(), PendingEdit::Reset));
+                }
+                set_text(&mut entity, text);
+            })
         })
     }
 
-    /// Reactively set the text of this input. If the signal outputs [`None`] the text is set to an empty string.
+    /// Reactively set the text of this input. If the signal outputs [`None`] the text is set to an empty string. Whether
+    /// the new text can be undone is configured with [`.text_signal_history`](TextInput::text_signal_history).
     pub fn text_signal<S: Signal<Item = impl Into<Option<String>>> + Send + 'static>(
         mut self,
         text_option_signal_option: impl Into<Option<S>>,
@@ -121,15 +143,14 @@ impl TextInput {
                     text_option_signal.map(|text_option| text_option.into().unwrap_or_default()),
                     |In((entity, text)): In<(Entity, String)>,
                      mut last_text_query: Query<&mut LastSignalText>,
-                     mut text_input_queues: Query<&mut TextInputQueue>| {
+                     mut histories: Query<&mut History>,
+                     mut commands: Commands| {
                         if let Ok(mut last_text) = last_text_query.get_mut(entity) {
                             // only queue an update if the incoming signal value is different
                             // from the last value we set from a signal. This prevents redundant updates.
                             if last_text.0 != text {
                                 last_text.0 = text.clone();
-                                if let Ok(mut queue) = text_input_queues.get_mut(entity) {
-                                    queue_set_text_actions(&mut queue, text);
-                                }
+                                set_signal_text(entity, text, &mut histories, &mut commands);
                             }
                         }
                     },
@@ -139,6 +160,27 @@ impl TextInput {
         self
     }
 
+    // like `.text_signal`, but sets the text even if the signal outputs the same text again, e.g.
+    // when reselecting a suggestion after editing the text, and leaves the text be on `None`
+    pub(crate) fn text_signal_forced(self, text_option_signal: impl Signal<Item = Option<String>> + Send + 'static) -> Self {
+        self.update_raw_el(|raw_el| {
+            raw_el.on_signal_with_system(
+                text_option_signal,
+                |In((entity, text_option)): In<(Entity, Option<String>)>,
+                 mut last_text_query: Query<&mut LastSignalText>,
+                 mut histories: Query<&mut History>,
+                 mut commands: Commands| {
+                    if let Some(text) = text_option {
+                        if let Ok(mut last_text) = last_text_query.get_mut(entity) {
+                            last_text.0 = text.clone();
+                        }
+                        set_signal_text(entity, text, &mut histories, &mut commands);
+                    }
+                },
+            )
+        })
+    }
+
     /// When this input's focused state changes, run a system which takes [`In`](`System::In`)
     /// this input's [`Entity`] and its current focused state.
     pub fn on_focused_change_with_system<Marker>(
@@ -215,7 +257,7 @@ impl TextInput {
                     commands.run_system_with(system, (change.target(), change.event().0.clone()));
                 });
             }))
-            .with_entity(|mut entity| { entity.insert_if_new((ListenToChanges, TextInputContents::default())); })
+            .with_entity(|mut entity| { entity.insert_if_new((ListenToChanges::default(), TextInputContents::default())); })
             .apply(remove_system_holder_on_remove(system_holder))
         })
     }
@@ -239,6 +281,418 @@ impl TextInput {
             },
         )
     }
+
+    /// When the text of this input is submitted, e.g. by pressing Enter, run a `handler` [`System`] which takes [`In`](System::In) this input's [`Entity`] and the submitted [`String`].
+    pub fn on_submit_with_system<Marker>(self, handler: impl IntoSystem<In<(Entity, String)>, (), Marker> + Send + 'static) -> Self {
+        self.update_raw_el(|raw_el| {
+            let system_holder = Arc::new(OnceLock::new());
+            raw_el.on_spawn(clone!((system_holder) move |world, entity| {
+                let system = register_system(world, handler);
+                let _ = system_holder.set(system);
+                observe(world, entity, move |submit: Trigger<TextInputSubmit>, mut commands: Commands| {
+                    commands.run_system_with(system, (submit.target(), submit.event().0.clone()));
+                });
+            }))
+            .with_entity(|mut entity| { entity.insert_if_new(ListenToSubmits); })
+            .apply(remove_system_holder_on_remove(system_holder))
+        })
+    }
+
+    /// When the text of this input is submitted, e.g. by pressing Enter, run a function with the submitted text.
+    pub fn on_submit(self, mut handler: impl FnMut(String) + Send + Sync + 'static) -> Self {
+        self.on_submit_with_system(move |In((_, text))| handler(text))
+    }
+
+    /// When Escape is pressed while this input is focused, run a `handler` [`System`] which takes [`In`](System::In) this input's [`Entity`].
+    pub fn on_cancel_with_system<Marker>(self, handler: impl IntoSystem<In<Entity>, (), Marker> + Send + 'static) -> Self {
+        self.update_raw_el(|raw_el| {
+            let system_holder = Arc::new(OnceLock::new());
+            raw_el.on_spawn(clone!((system_holder) move |world, entity| {
+                let system = register_system(world, handler);
+                let _ = system_holder.set(system);
+                observe(world, entity, move |cancel: Trigger<TextInputCancel>, mut commands: Commands| {
+                    commands.run_system_with(system, cancel.target());
+                });
+            }))
+            .with_entity(|mut entity| { entity.insert_if_new(ListenToCancels); })
+            .apply(remove_system_holder_on_remove(system_holder))
+        })
+    }
+
+    /// When Escape is pressed while this input is focused, run a function.
+    pub fn on_cancel(self, mut handler: impl FnMut() + Send + Sync + 'static) -> Self {
+        self.on_cancel_with_system(move |_: In<Entity>| handler())
+    }
+
+    /// Submit the text of this input when it loses focus, like pressing Enter would.
+    pub fn submit_on_blur(mut self, submit_on_blur_option: impl Into<Option<bool>>) -> Self {
+        if Into::<Option<bool>>::into(submit_on_blur_option).unwrap_or(false) {
+            self = self.update_raw_el(|raw_el| {
+                raw_el
+                .with_entity(|mut entity| { entity.insert_if_new(Focusable { is_focused: false }); })
+                .observe(|focused_change: Trigger<FocusedChange>, mut text_input_queues: Query<&mut TextInputQueue>| {
+                    if focused_change.event().0.not() && let Ok(mut queue) = text_input_queues.get_mut(focused_change.target()) {
+                        queue.add(TextInputAction::Submit);
+                    }
+                })
+            });
+        }
+        self
+    }
+
+    /// Set whether the text of this input is cleared when it is submitted, `false` by default.
+    pub fn clear_on_submit(self, clear_on_submit_option: impl Into<Option<bool>>) -> Self {
+        let clear_on_submit = clear_on_submit_option.into().unwrap_or(false);
+        self.with_text_input_node(move |mut text_input_node| text_input_node.clear_on_submit = clear_on_submit)
+    }
+
+    /// Reactively set whether the text of this input is cleared when it is submitted.
+    pub fn clear_on_submit_signal<S: Signal<Item = bool> + Send + 'static>(
+        mut self,
+        clear_on_submit_signal_option: impl Into<Option<S>>,
+    ) -> Self {
+        if let Some(clear_on_submit_signal) = clear_on_submit_signal_option.into() {
+            self = self.on_signal_with_text_input_node(clear_on_submit_signal, |mut text_input_node, clear_on_submit| {
+                text_input_node.clear_on_submit = clear_on_submit;
+            });
+        }
+        self
+    }
+
+    /// Only allow edits which leave every character of the text of this input passing the `filter`; rejected edits are dropped before they reach the text. Replaces any previous filter, including one set by a [`TextInputPreset`].
+    pub fn filter(self, filter: impl Fn(char) -> bool + Send + Sync + 'static) -> Self {
+        self.with_text_input_node(|mut text_input_node| text_input_node.filter = None)
+            .filter_text(Some(TextFilter(Box::new(move |text| text.chars().all(&filter)))))
+    }
+
+    fn filter_text(self, filter_option: Option<TextFilter>) -> Self {
+        self.update_raw_el(|raw_el| {
+            raw_el.with_entity(move |mut entity| {
+                if let Some(filter) = filter_option {
+                    entity.insert(filter);
+                } else {
+                    entity.remove::<TextFilter>();
+                }
+            })
+        })
+    }
+
+    /// Set the maximum number of characters of the text of this input; edits which would exceed it are rejected.
+    pub fn max_length(self, max_length_option: impl Into<Option<usize>>) -> Self {
+        let max_length_option = max_length_option.into();
+        self.with_text_input_node(move |mut text_input_node| text_input_node.max_chars = max_length_option)
+    }
+
+    /// Reactively set the maximum number of characters of the text of this input. If the [`Signal`] outputs [`None`], the length is unlimited.
+    pub fn max_length_signal<S: Signal<Item = impl Into<Option<usize>> + 'static> + Send + 'static>(
+        mut self,
+        max_length_option_signal_option: impl Into<Option<S>>,
+    ) -> Self {
+        if let Some(max_length_option_signal) = max_length_option_signal_option.into() {
+            self = self.on_signal_with_text_input_node(max_length_option_signal.map(Into::into), |mut text_input_node, max_length_option| {
+                text_input_node.max_chars = max_length_option;
+            });
+        }
+        self
+    }
+
+    /// Validate the text of this input whenever it changes, outputting the error of the first failing validator to [`.error_signal`](TextInput::error_signal). This method can be called repeatedly to register many validators.
+    pub fn validate(self, validator: impl Fn(&str) -> Result<(), String> + Send + Sync + 'static) -> Self {
+        let error = self.error.clone();
+        self.update_raw_el(|raw_el| {
+            raw_el.with_entity(move |mut entity| {
+                entity.insert_if_new((ListenToChanges::default(), TextInputContents::default(), ValidationError(error), Validators::default()));
+                if let Some(mut validators) = entity.get_mut::<Validators>() {
+                    validators.0.push(Box::new(validator));
+                }
+            })
+        })
+    }
+
+    /// [`Signal`] of this input's validation error, [`None`] if the text passes every [`.validate`](TextInput::validate) validator; useful for styling the input or showing the error beside it.
+    pub fn error_signal(&self) -> impl Signal<Item = Option<String>> + Send + 'static {
+        self.error.signal_cloned()
+    }
+
+    /// Filter and validate the text of this input according to a [`TextInputPreset`].
+    pub fn preset(self, preset: TextInputPreset) -> Self {
+        let input_filter = preset.input_filter();
+        self.with_text_input_node(move |mut text_input_node| text_input_node.filter = input_filter)
+            .filter_text(preset.partial_match_filter())
+            .validate(move |text| preset.validate(text))
+    }
+
+    /// Mask the text of this input, e.g. for passwords, see [`ObscuredSettings`]. Handlers and
+    /// validators still receive the real text.
+    pub fn obscured(self, obscured_option: impl Into<Option<ObscuredSettings>>) -> Self {
+        self.obscured_signal(always(obscured_option.into()))
+    }
+
+    /// Reactively mask the text of this input. If the [`Signal`] outputs [`None`], the real text is
+    /// shown, so e.g. an "eye" button's pressed state can reveal the text while it is held.
+    pub fn obscured_signal<S: Signal<Item = impl Into<Option<ObscuredSettings>> + 'static> + Send + 'static>(
+        mut self,
+        obscured_option_signal_option: impl Into<Option<S>>,
+    ) -> Self {
+        if let Some(obscured_option_signal) = obscured_option_signal_option.into() {
+            self = self.update_raw_el(|raw_el| {
+                raw_el
+                .on_signal_with_system(
+                    obscured_option_signal.map(Into::into),
+                    |In((entity, settings_option)): In<(Entity, Option<ObscuredSettings>)>,
+                     mut inputs: Query<(&mut TextInputBuffer, Option<&mut Obscured>)>,
+                     mut commands: Commands| {
+                        let Ok((mut buffer, obscured_option)) = inputs.get_mut(entity) else { return };
+                        match (settings_option, obscured_option) {
+                            (Some(settings), Some(mut obscured)) => {
+                                if obscured.settings != settings {
+                                    obscured.settings = settings;
+                                    obscured.revealed = false;
+                                    let masked = obscured.masked();
+                                    set_buffer_text(&mut buffer, &masked);
+                                }
+                            }
+                            (Some(settings), None) => {
+                                let obscured = Obscured { settings, text: buffer.get_text(), revealed: false };
+                                set_buffer_text(&mut buffer, &obscured.masked());
+                                commands.entity(entity).insert(obscured);
+                            }
+                            (None, Some(obscured)) => {
+                                set_buffer_text(&mut buffer, &obscured.text);
+                                commands.entity(entity).remove::<Obscured>();
+                            }
+                            (None, None) => (),
+                        }
+                    },
+                )
+            });
+        }
+        self
+    }
+
+    /// Prevent editing the text of this input while still allowing it to be selected and copied.
+    /// Edits are dropped before they reach it, while its text can still be set via
+    /// [`.text_signal`](TextInput::text_signal).
+    pub fn read_only(self, read_only_option: impl Into<Option<bool>>) -> Self {
+        self.read_only_signal(always(read_only_option.into().unwrap_or(false)))
+    }
+
+    /// Reactively prevent editing the text of this input, see [`.read_only`](TextInput::read_only).
+    pub fn read_only_signal<S: Signal<Item = bool> + Send + 'static>(mut self, read_only_signal_option: impl Into<Option<S>>) -> Self {
+        if let Some(read_only_signal) = read_only_signal_option.into() {
+            self = self.update_raw_el(|raw_el| {
+                raw_el.on_signal_with_system(
+                    read_only_signal.dedupe(),
+                    |In((entity, read_only)): In<(Entity, bool)>, mut commands: Commands| {
+                        if let Ok(mut entity) = commands.get_entity(entity) {
+                            if read_only {
+                                entity.try_insert(ReadOnly);
+                            } else {
+                                entity.remove::<ReadOnly>();
+                            }
+                        }
+                    },
+                )
+            });
+        }
+        self
+    }
+
+    /// [`Signal`] of the index of the character before which this input's caret is.
+    pub fn caret_signal(&self) -> impl Signal<Item = usize> + Send + 'static {
+        self.caret.signal()
+    }
+
+    /// Sync a [`Mutable`] with the index of the character before which this input's caret is.
+    pub fn caret_sync(self, caret: Mutable<usize>) -> Self {
+        let caret_signal = self.caret_signal();
+        self.update_raw_el(|raw_el| raw_el.on_signal_with_system(caret_signal, move |In((_, index)): In<(Entity, usize)>| caret.set_neq(index)))
+    }
+
+    /// [`Signal`] of the range of characters selected in this input, [`None`] if nothing is selected.
+    pub fn selection_signal(&self) -> impl Signal<Item = Option<Range<usize>>> + Send + 'static {
+        self.selection.signal_cloned()
+    }
+
+    /// Select a range of characters in this input, clamped to its text, moving the caret to the end of the range; an empty range just moves the caret, and [`None`] clears the selection.
+    pub fn set_selection(self, selection_option: impl Into<Option<Range<usize>>>) -> Self {
+        self.set_selection_signal(always(selection_option.into()))
+    }
+
+    /// Reactively select a range of characters in this input, see [`.set_selection`](TextInput::set_selection).
+    pub fn set_selection_signal<S: Signal<Item = impl Into<Option<Range<usize>>> + 'static> + Send + 'static>(
+        mut self,
+        selection_option_signal_option: impl Into<Option<S>>,
+    ) -> Self {
+        if let Some(selection_option_signal) = selection_option_signal_option.into() {
+            self = self.update_raw_el(|raw_el| {
+                raw_el.on_signal_with_system(
+                    selection_option_signal.map(Into::into),
+                    |In((entity, selection_option)): In<(Entity, Option<Range<usize>>)>, mut buffers: Query<&mut TextInputBuffer>| {
+                        if let Ok(mut buffer) = buffers.get_mut(entity) {
+                            set_selection(&mut buffer, selection_option);
+                        }
+                    },
+                )
+            });
+        }
+        self
+    }
+
+    /// Select all the text of this input when it gains focus, e.g. so it can be replaced or copied at once.
+    pub fn select_all_on_focus(mut self, select_all_on_focus_option: impl Into<Option<bool>>) -> Self {
+        if Into::<Option<bool>>::into(select_all_on_focus_option).unwrap_or(false) {
+            self = self.update_raw_el(|raw_el| {
+                raw_el
+                .with_entity(|mut entity| { entity.insert_if_new(Focusable { is_focused: false }); })
+                .observe(|focused_change: Trigger<FocusedChange>, mut buffers: Query<&mut TextInputBuffer>| {
+                    if focused_change.event().0 && let Ok(mut buffer) = buffers.get_mut(focused_change.target()) {
+                        set_selection(&mut buffer, Some(0..usize::MAX));
+                    }
+                })
+            });
+        }
+        self
+    }
+
+    /// When this input is clicked, select the whole word under the caret, e.g. for IDs.
+    pub fn select_word(mut self, select_word_option: impl Into<Option<bool>>) -> Self {
+        if Into::<Option<bool>>::into(select_word_option).unwrap_or(false) {
+            self = self.update_raw_el(|raw_el| {
+                raw_el.observe(|click: Trigger<Pointer<Click>>, mut buffers: Query<&mut TextInputBuffer>| {
+                    if matches!(click.event().button, PointerButton::Primary) && let Ok(mut buffer) = buffers.get_mut(click.target()) {
+                        let cursor = buffer.editor.cursor();
+                        buffer.editor.set_selection(Selection::Word(cursor));
+                    }
+                })
+            });
+        }
+        self
+    }
+
+    /// [`Signal`] of whether this input has edits which can be undone, e.g. to enable an undo button.
+    pub fn can_undo_signal(&self) -> impl Signal<Item = bool> + Send + 'static {
+        self.can_undo.signal()
+    }
+
+    /// [`Signal`] of whether this input has undone edits which can be redone.
+    pub fn can_redo_signal(&self) -> impl Signal<Item = bool> + Send + 'static {
+        self.can_redo.signal()
+    }
+
+    /// Set whether Ctrl+Z undoes and Ctrl+Y or Ctrl+Shift+Z redoes edits while this input is focused, with Cmd in place of Ctrl on macOS, `true` by default; either way, edits can be undone via [`TextInputCommands`].
+    pub fn undo_key_bindings(self, undo_key_bindings: bool) -> Self {
+        self.update_raw_el(|raw_el| raw_el.with_component::<History>(move |mut history| history.key_bindings = undo_key_bindings))
+    }
+
+    /// Set whether text set via [`.text_signal`](TextInput::text_signal) is recorded as an undoable edit or resets the undo history, see [`TextSignalHistory`].
+    pub fn text_signal_history(self, text_signal_history: TextSignalHistory) -> Self {
+        self.update_raw_el(|raw_el| raw_el.with_component::<History>(move |mut history| history.text_signal_history = text_signal_history))
+    }
+
+    /// Show placeholder text while this input is empty; it is hidden while this input is focused
+    /// unless [`.placeholder_on_focus`](TextInput::placeholder_on_focus) is set.
+    pub fn placeholder(self, placeholder_option: impl Into<Option<String>>) -> Self {
+        self.placeholder_signal(always(placeholder_option.into()))
+    }
+
+    /// Reactively set the placeholder text, e.g. to describe why the current text is invalid. If
+    /// the [`Signal`] outputs [`None`], no placeholder is shown.
+    pub fn placeholder_signal<S: Signal<Item = impl Into<Option<String>> + 'static> + Send + 'static>(
+        mut self,
+        placeholder_option_signal_option: impl Into<Option<S>>,
+    ) -> Self {
+        if let Some(placeholder_option_signal) = placeholder_option_signal_option.into() {
+            self = self.update_raw_el(|raw_el| {
+                raw_el
+                .with_entity(|mut entity| { entity.insert_if_new((Placeholder::default(), TextInputPrompt::new(""))); })
+                .on_signal_with_component::<Option<String>, Placeholder>(
+                    placeholder_option_signal.map(Into::into),
+                    |mut placeholder, text_option| placeholder.text = text_option.unwrap_or_default(),
+                )
+            });
+        }
+        self
+    }
+
+    /// Set the color of the placeholder text; defaults to [`TextInputPrompt`]'s color.
+    pub fn placeholder_color(self, color_option: impl Into<Option<Color>>) -> Self {
+        self.placeholder_color_signal(always(color_option.into()))
+    }
+
+    /// Reactively set the color of the placeholder text, e.g. to turn it red once validation fails.
+    pub fn placeholder_color_signal<S: Signal<Item = impl Into<Option<Color>> + 'static> + Send + 'static>(
+        mut self,
+        color_option_signal_option: impl Into<Option<S>>,
+    ) -> Self {
+        if let Some(color_option_signal) = color_option_signal_option.into() {
+            self = self.update_raw_el(|raw_el| {
+                raw_el
+                .with_entity(|mut entity| { entity.insert_if_new((Placeholder::default(), TextInputPrompt::new(""))); })
+                .on_signal_with_component::<Option<Color>, Placeholder>(
+                    color_option_signal.map(Into::into),
+                    |mut placeholder, color_option| placeholder.color = color_option,
+                )
+            });
+        }
+        self
+    }
+
+    /// Set whether the placeholder stays visible while this input is focused but empty, `false` by
+    /// default.
+    pub fn placeholder_on_focus(self, placeholder_on_focus: bool) -> Self {
+        self.update_raw_el(|raw_el| {
+            raw_el
+            .with_entity(|mut entity| { entity.insert_if_new((Placeholder::default(), TextInputPrompt::new(""))); })
+            .with_component::<Placeholder>(move |mut placeholder| placeholder.on_focus = placeholder_on_focus)
+        })
+    }
+
+    /// Show the placeholder as a label which, once this input is focused or has text, floats above
+    /// it rather than disappearing, see [`FloatingLabel`].
+    pub fn floating_label(self, floating_label_option: impl Into<Option<FloatingLabel>>) -> Self {
+        let floating_label_option = floating_label_option.into();
+        self.update_raw_el(|raw_el| {
+            raw_el
+            .with_entity(|mut entity| { entity.insert_if_new((Placeholder::default(), TextInputPrompt::new(""))); })
+            .with_component::<Placeholder>(move |mut placeholder| placeholder.floating = floating_label_option)
+            .child(
+                floating_label_option.is_some().then(|| {
+                    El::<Text>::new()
+                    .with_node(|mut node| node.position_type = PositionType::Absolute)
+                    .update_raw_el(|raw_el| raw_el.insert((FloatingLabelText::default(), Pickable::IGNORE)))
+                })
+            )
+        })
+    }
+
+    /// Transform the text copied or cut from this input before it reaches the
+    /// [`TextInputClipboard`], or veto the copy by returning [`None`]. This method can be called
+    /// repeatedly, each hook receiving the output of the previous one.
+    pub fn on_copy(self, hook: impl Fn(String) -> Option<String> + Send + Sync + 'static) -> Self {
+        self.update_raw_el(|raw_el| {
+            raw_el.with_entity(move |mut entity| {
+                entity.insert_if_new(ClipboardHooks::default());
+                if let Some(mut hooks) = entity.get_mut::<ClipboardHooks>() {
+                    hooks.copy.push(Box::new(hook));
+                }
+            })
+        })
+    }
+
+    /// Transform the text pasted into this input, or veto the paste by returning [`None`]; newlines
+    /// are already stripped from text pasted into single line inputs. This method can be called
+    /// repeatedly, each hook receiving the output of the previous one.
+    pub fn on_paste(self, hook: impl Fn(String) -> Option<String> + Send + Sync + 'static) -> Self {
+        self.update_raw_el(|raw_el| {
+            raw_el.with_entity(move |mut entity| {
+                entity.insert_if_new(ClipboardHooks::default());
+                if let Some(mut hooks) = entity.get_mut::<ClipboardHooks>() {
+                    hooks.paste.push(Box::new(hook));
+                }
+            })
+        })
+    }
 }
 
 /// A component to store the last text value that was successfully applied by [`TextInput::text_signal`].
@@ -246,28 +700,799 @@ impl TextInput {
 #[derive(Component, Default)]
 struct LastSignalText(String);
 
-fn queue_set_text_actions(
-    text_input_queue: &mut TextInputQueue,
+fn set_signal_text(entity: Entity, text: String, histories: &mut Query<&mut History>, commands: &mut Commands) {
+    if let Ok(mut history) = histories.get_mut(entity) {
+        // the initial text is never undone
+        let edit = if history.undo.is_empty() && history.current.text.is_empty() {
+            PendingEdit::Reset
+        } else {
+            match history.text_signal_history {
+                TextSignalHistory::Record => PendingEdit::Record,
+                TextSignalHistory::Reset => PendingEdit::Reset,
+            }
+        };
+        history.pending = Some((text.clone(), edit));
+    }
+    commands.entity(entity).queue(move |mut entity: EntityWorldMut| set_text(&mut entity, text));
+}
+
+// set the text of an input directly rather than through its queue, so it isn't dropped while the
+// input is read only or rejected by its filter, moving the caret to the end
+pub(crate) fn set_text(entity: &mut EntityWorldMut, text: String) {
+    let shown = match entity.get_mut::<Obscured>() {
+        Some(mut obscured) => {
+            obscured.text = text;
+            obscured.shown()
+        }
+        None => text,
+    };
+    if let Some(mut buffer) = entity.get_mut::<TextInputBuffer>() {
+        set_buffer_text(&mut buffer, &shown);
+        set_selection(&mut buffer, Some(usize::MAX..usize::MAX));
+    }
+}
+
+// the latest text reported to change handlers, `None` until the first report
+#[derive(Component, Default)]
+struct ListenToChanges(Option<String>);
+
+#[derive(Event)]
+struct TextInputChange(String);
+
+#[allow(clippy::type_complexity)]
+fn on_change(
+    mut inputs: Query<
+        (Entity, &TextInputContents, Option<&Obscured>, &mut ListenToChanges),
+        Or<(Changed<TextInputContents>, Changed<Obscured>)>,
+    >,
+    mut commands: Commands,
+) {
+    for (entity, contents, obscured_option, mut listen_to_changes) in inputs.iter_mut() {
+        let text = obscured_option.map(|obscured| obscured.text.as_str()).unwrap_or(contents.get());
+        // revealing or masking an obscured input changes its buffer but not its text
+        if listen_to_changes.0.as_deref() != Some(text) {
+            listen_to_changes.0 = Some(text.to_string());
+            commands.trigger_targets(TextInputChange(text.to_string()), entity);
+        }
+    }
+}
+
+/// How text set via [`TextInput::text_signal`] affects the undo history of a [`TextInput`].
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub enum TextSignalHistory {
+    /// The new text is recorded as an edit, so undoing restores the previous text.
+    #[default]
+    Record,
+    /// The undo history is cleared, e.g. when the input is loaded with unrelated text.
+    Reset,
+}
+
+/// Programmatic undo and redo of a [`TextInput`] [`Entity`].
+pub trait TextInputCommands {
+    /// Undo the latest edit of the input, if any.
+    fn undo(&mut self) -> &mut Self;
+
+    /// Redo the latest undone edit of the input, if any.
+    fn redo(&mut self) -> &mut Self;
+}
+
+impl TextInputCommands for EntityCommands<'_> {
+    fn undo(&mut self) -> &mut Self {
+        self.queue(|entity: EntityWorldMut| step_history(entity, true))
+    }
+
+    fn redo(&mut self) -> &mut Self {
+        self.queue(|entity: EntityWorldMut| step_history(entity, false))
+    }
+}
+
+// consecutive insertions or deletions of single characters closer together than this, in seconds,
+// are undone together
+const COALESCE_SECONDS: f32 = 1.;
+// the most edits which can be undone
+const MAX_HISTORY: usize = 100;
+
+#[derive(Clone, Default)]
+struct Snapshot {
     text: String,
+    caret: usize,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum EditKind {
+    Insert,
+    Delete,
+}
+
+enum PendingEdit {
+    Record,
+    Reset,
+    // undo or redo, which already moved the snapshots between the stacks
+    Restore(usize),
+}
+
+#[derive(Component)]
+struct History {
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    current: Snapshot,
+    // the kind and time of the latest typed edit, which the next one may be coalesced with
+    last_edit: Option<(EditKind, f32)>,
+    // the text a programmatic change will produce and how it should be recorded
+    pending: Option<(String, PendingEdit)>,
+    key_bindings: bool,
+    text_signal_history: TextSignalHistory,
+    can_undo: Mutable<bool>,
+    can_redo: Mutable<bool>,
+}
+
+impl History {
+    fn new(can_undo: Mutable<bool>, can_redo: Mutable<bool>) -> Self {
+        Self {
+            undo: vec![],
+            redo: vec![],
+            current: Snapshot::default(),
+            last_edit: None,
+            pending: None,
+            key_bindings: true,
+            text_signal_history: TextSignalHistory::default(),
+            can_undo,
+            can_redo,
+        }
+    }
+
+    fn push_undo(&mut self, snapshot: Snapshot) {
+        self.undo.push(snapshot);
+        if self.undo.len() > MAX_HISTORY {
+            self.undo.remove(0);
+        }
+    }
+
+    fn sync_signals(&self) {
+        self.can_undo.set_neq(!self.undo.is_empty());
+        self.can_redo.set_neq(!self.redo.is_empty());
+    }
+}
+
+fn step_history(mut entity: EntityWorldMut, undo: bool) {
+    if entity.contains::<ReadOnly>() {
+        return;
+    }
+    let Some(mut history) = entity.get_mut::<History>() else { return };
+    let snapshot_option = if undo { history.undo.pop() } else { history.redo.pop() };
+    let Some(snapshot) = snapshot_option else { return };
+    let current = history.current.clone();
+    if undo {
+        history.redo.push(current);
+    } else {
+        history.push_undo(current);
+    }
+    history.last_edit = None;
+    history.pending = Some((snapshot.text.clone(), PendingEdit::Restore(snapshot.caret)));
+    history.sync_signals();
+    set_text(&mut entity, snapshot.text);
+}
+
+// `bevy_ui_text_input` only maps Ctrl+Shift+Z to redo on macOS
+#[cfg(not(target_os = "macos"))]
+fn redo_on_ctrl_shift_z(
+    keyboard_input: Trigger<FocusedInput<KeyboardInput>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut queues: Query<&mut TextInputQueue>,
+) {
+    let KeyboardInput { logical_key, state, .. } = &keyboard_input.event().input;
+    if state.is_pressed()
+        && matches!(logical_key, Key::Character(c) if c.eq_ignore_ascii_case("z"))
+        && keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight])
+        && keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight])
+        && let Ok(mut queue) = queues.get_mut(keyboard_input.target())
+    {
+        queue.add(TextInputAction::Edit(actions::TextInputEdit::Redo));
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn record_history(
+    time: Res<Time>,
+    mut inputs: Query<(&TextInputContents, &mut TextInputBuffer, &mut History, Option<&Obscured>), Or<(Changed<TextInputContents>, Changed<Obscured>)>>,
 ) {
-    for action in [
-        TextInputAction::Edit(actions::TextInputEdit::SelectAll),
-        TextInputAction::Edit(actions::TextInputEdit::Paste(text)),
-    ] {
-        text_input_queue.add(action);
+    for (contents, mut buffer, mut history, obscured_option) in inputs.iter_mut() {
+        let text = obscured_option.map(|obscured| obscured.text.as_str()).unwrap_or(contents.get());
+        if text == history.current.text {
+            continue;
+        }
+        let cursor = buffer.editor.cursor();
+        let caret = buffer.editor.with_buffer(|cosmic_buffer| char_index(cosmic_buffer, cursor));
+        let snapshot = Snapshot { text: text.to_string(), caret };
+        match history.pending.take_if(|(pending, _)| pending.as_str() == text).map(|(_, edit)| edit) {
+            Some(PendingEdit::Restore(caret)) => {
+                set_selection(&mut buffer, Some(caret..caret));
+                history.current = Snapshot { caret, ..snapshot };
+            }
+            Some(PendingEdit::Reset) => {
+                history.undo.clear();
+                history.redo.clear();
+                history.current = snapshot;
+                history.last_edit = None;
+            }
+            Some(PendingEdit::Record) => {
+                let current = mem::replace(&mut history.current, snapshot);
+                history.push_undo(current);
+                history.redo.clear();
+                history.last_edit = None;
+            }
+            None => {
+                let (len, previous_len) = (text.chars().count(), history.current.text.chars().count());
+                let kind = if len > previous_len { EditKind::Insert } else { EditKind::Delete };
+                let now = time.elapsed_secs();
+                let coalesce = len.abs_diff(previous_len) == 1
+                    && history.last_edit.is_some_and(|(last_kind, last_time)| last_kind == kind && now - last_time < COALESCE_SECONDS);
+                let current = mem::replace(&mut history.current, snapshot);
+                if !coalesce {
+                    history.push_undo(current);
+                }
+                history.redo.clear();
+                history.last_edit = Some((kind, now));
+            }
+        }
+        history.sync_signals();
+    }
+}
+
+type ClipboardHook = Box<dyn Fn(String) -> Option<String> + Send + Sync>;
+
+#[derive(Component, Default)]
+struct ClipboardHooks {
+    copy: Vec<ClipboardHook>,
+    paste: Vec<ClipboardHook>,
+}
+
+fn run_hooks(hooks: Option<&[ClipboardHook]>, text: String) -> Option<String> {
+    hooks.unwrap_or_default().iter().try_fold(text, |text, hook| hook(text))
+}
+
+/// How a [`TextInput`]'s placeholder floats above it as a label, see
+/// [`TextInput::floating_label`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FloatingLabel {
+    /// The size of the floated label relative to the input's font, `0.75` by default.
+    pub scale: f32,
+    /// The gap, in logical pixels, between the floated label and the top of the input, `2.` by
+    /// default.
+    pub gap: f32,
+    /// How long the label takes to float up or settle back down, 150 milliseconds by default.
+    pub duration: Duration,
+}
+
+impl Default for FloatingLabel {
+    fn default() -> Self {
+        Self { scale: 0.75, gap: 2., duration: Duration::from_millis(150) }
+    }
+}
+
+#[derive(Component, Default)]
+struct Placeholder {
+    text: String,
+    color: Option<Color>,
+    // the prompt's own color, captured before `color` first overrides it, restored once `color` is
+    // unset again
+    prompt_color: Option<Option<Color>>,
+    on_focus: bool,
+    floating: Option<FloatingLabel>,
+}
+
+// how far, from `0.` to `1.`, a floating label has floated up
+#[derive(Component, Default)]
+struct FloatingLabelText(f32);
+
+fn sync_placeholders(
+    focused_option: Res<InputFocus>,
+    mut inputs: Query<(Entity, &mut Placeholder, Ref<TextInputContents>, &mut TextInputPrompt)>,
+) {
+    for (entity, mut placeholder, contents, mut prompt) in inputs.iter_mut() {
+        if !(focused_option.is_changed() || placeholder.is_changed() || contents.is_changed()) {
+            continue;
+        }
+        let shown = placeholder.floating.is_none()
+            && contents.get().is_empty()
+            && (placeholder.on_focus || focused_option.0 != Some(entity));
+        let text = if shown { placeholder.text.as_str() } else { "" };
+        if prompt.text != text {
+            prompt.text = text.to_string();
+        }
+        if placeholder.is_changed() {
+            let prompt_color = *placeholder.bypass_change_detection().prompt_color.get_or_insert(prompt.color);
+            let color = placeholder.color.or(prompt_color);
+            if prompt.color != color {
+                prompt.color = color;
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn animate_floating_labels(
+    time: Res<Time>,
+    focused_option: Res<InputFocus>,
+    inputs: Query<(&Placeholder, &TextInputContents, &TextFont, &TextColor, Option<&TextInputPrompt>, &ComputedNode)>,
+    mut labels: Query<(&mut FloatingLabelText, &ChildOf, &mut Node, &mut Text, &mut TextFont, &mut TextColor), Without<Placeholder>>,
+) {
+    for (mut progress, child_of, mut node, mut text, mut font, mut color) in labels.iter_mut() {
+        let Ok((placeholder, contents, input_font, input_color, prompt_option, computed_node)) = inputs.get(child_of.parent()) else { continue };
+        let Some(settings) = placeholder.floating else {
+            if node.display != Display::None {
+                node.display = Display::None;
+            }
+            continue;
+        };
+        if node.display != Display::Flex {
+            node.display = Display::Flex;
+        }
+        let floated = focused_option.0 == Some(child_of.parent()) || !contents.get().is_empty();
+        let step = if settings.duration.is_zero() { 1. } else { time.delta_secs() / settings.duration.as_secs_f32() };
+        let target = if floated { 1. } else { 0. };
+        if progress.0 != target {
+            progress.0 = if floated { (progress.0 + step).min(1.) } else { (progress.0 - step).max(0.) };
+        }
+        let eased = EaseFunction::QuadraticOut.sample_clamped(progress.0);
+        // `ComputedNode` is in physical pixels
+        let padding = computed_node.padding();
+        let (top, left) = (padding.top * computed_node.inverse_scale_factor(), padding.left * computed_node.inverse_scale_factor());
+        let floated_size = input_font.font_size * settings.scale;
+        // `LineHeight` defaults to 1.2 times the font size
+        let floated_top = -floated_size * 1.2 - settings.gap;
+        let font_size = input_font.font_size + (floated_size - input_font.font_size) * eased;
+        if font.font_size != font_size {
+            font.font_size = font_size;
+        }
+        let (top, left) = (Val::Px(top + (floated_top - top) * eased), Val::Px(left));
+        if node.top != top || node.left != left {
+            node.top = top;
+            node.left = left;
+        }
+        if text.0 != placeholder.text {
+            text.0.clone_from(&placeholder.text);
+        }
+        // like the placeholder it replaces
+        let label_color = placeholder.color.or(prompt_option.and_then(|prompt| prompt.color)).unwrap_or(input_color.0);
+        if color.0 != label_color {
+            color.0 = label_color;
+        }
     }
 }
 
 #[derive(Component)]
-struct ListenToChanges;
+struct SelectionState {
+    caret: Mutable<usize>,
+    selection: Mutable<Option<Range<usize>>>,
+}
 
-#[derive(Event)]
-struct TextInputChange(String);
+fn set_selection(buffer: &mut TextInputBuffer, selection_option: Option<Range<usize>>) {
+    let editor = &mut buffer.editor;
+    match selection_option {
+        Some(Range { start, end }) => {
+            let (start, end) = editor.with_buffer(|buffer| (char_cursor(buffer, start), char_cursor(buffer, end)));
+            editor.set_selection(if start == end { Selection::None } else { Selection::Normal(start) });
+            editor.set_cursor(end);
+        }
+        None => editor.set_selection(Selection::None),
+    }
+}
+
+// the caret and the selected range, if any, in characters
+fn caret_and_selection(buffer: &TextInputBuffer) -> (usize, Option<Range<usize>>) {
+    let editor = &buffer.editor;
+    editor.with_buffer(|buffer| {
+        (
+            char_index(buffer, editor.cursor()),
+            editor
+                .selection_bounds()
+                .map(|(start, end)| char_index(buffer, start)..char_index(buffer, end))
+                .filter(|range| !range.is_empty()),
+        )
+    })
+}
+
+fn sync_selection_states(inputs: Query<(&TextInputBuffer, &SelectionState)>) {
+    for (buffer, SelectionState { caret, selection }) in inputs.iter() {
+        let (caret_index, selection_option) = caret_and_selection(buffer);
+        caret.set_neq(caret_index);
+        selection.set_neq(selection_option);
+    }
+}
+
+/// How an obscured [`TextInput`] masks its text, see [`TextInput::obscured`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ObscuredSettings {
+    /// The character shown in place of each character of the text, `•` by default.
+    pub mask: char,
+    /// While this key is held and the input is focused, its real text is shown.
+    pub reveal_on_hold: Option<KeyCode>,
+}
+
+impl Default for ObscuredSettings {
+    fn default() -> Self {
+        Self { mask: '•', reveal_on_hold: None }
+    }
+}
+
+#[derive(Component)]
+struct Obscured {
+    settings: ObscuredSettings,
+    // the real text, while the buffer holds the masked text
+    text: String,
+    revealed: bool,
+}
+
+impl Obscured {
+    fn masked(&self) -> String {
+        self.text.chars().map(|_| self.settings.mask).collect()
+    }
+
+    fn shown(&self) -> String {
+        if self.revealed { self.text.clone() } else { self.masked() }
+    }
+}
+
+// index of the character after a cursor within the text of a buffer, whose lines are joined by
+// newlines
+fn char_index(buffer: &Buffer, cursor: Cursor) -> usize {
+    let before_line = buffer.lines.iter().take(cursor.line).map(|line| line.text().chars().count() + 1).sum::<usize>();
+    let within_line = buffer
+        .lines
+        .get(cursor.line)
+        .and_then(|line| line.text().get(..cursor.index))
+        .map(|before| before.chars().count())
+        .unwrap_or_default();
+    before_line + within_line
+}
+
+// cursor before the character at an index within the text of a buffer, clamped to its end
+fn char_cursor(buffer: &Buffer, mut index: usize) -> Cursor {
+    for (line_index, line) in buffer.lines.iter().enumerate() {
+        let len = line.text().chars().count();
+        if index <= len {
+            let byte_index = line.text().char_indices().nth(index).map(|(byte_index, _)| byte_index).unwrap_or(line.text().len());
+            return Cursor::new(line_index, byte_index);
+        }
+        index -= len + 1;
+    }
+    let last = buffer.lines.len().saturating_sub(1);
+    Cursor::new(last, buffer.lines.get(last).map(|line| line.text().len()).unwrap_or_default())
+}
+
+// replace the text of a buffer, keeping the cursor before the same character
+fn set_buffer_text(buffer: &mut TextInputBuffer, text: &str) {
+    let editor = &mut buffer.editor;
+    let cursor = editor.cursor();
+    let (caret, end, empty) = editor.with_buffer(|buffer| (char_index(buffer, cursor), char_cursor(buffer, usize::MAX), buffer.lines.is_empty()));
+    editor.set_selection(Selection::None);
+    // a buffer has no lines until its input is first laid out
+    if !empty {
+        editor.delete_range(Cursor::new(0, 0), end);
+    }
+    editor.insert_at(Cursor::new(0, 0), text, None);
+    let cursor = editor.with_buffer(|buffer| char_cursor(buffer, caret));
+    editor.set_cursor(cursor);
+    editor.set_redraw(true);
+}
+
+fn reveal_obscured_on_hold(
+    keys: Res<ButtonInput<KeyCode>>,
+    focused_option: Res<InputFocus>,
+    mut inputs: Query<(Entity, &mut TextInputBuffer, &mut Obscured)>,
+) {
+    for (entity, mut buffer, mut obscured) in inputs.iter_mut() {
+        let reveal = obscured.settings.reveal_on_hold.is_some_and(|key| keys.pressed(key)) && focused_option.0 == Some(entity);
+        if reveal != obscured.revealed {
+            obscured.revealed = reveal;
+            set_buffer_text(&mut buffer, &obscured.shown());
+        }
+    }
+}
+
+#[derive(Component)]
+struct ReadOnly;
+
+// whether an edit changes the text, rather than the caret, selection, or scroll
+fn edits_text(edit: &actions::TextInputEdit) -> bool {
+    use actions::TextInputEdit::*;
+    matches!(edit, Insert(..) | Enter | Backspace | Delete | Indent | Unindent | Paste(_))
+}
 
+// apply a text edit to the real text of an obscured input, in characters, returning the new text
+// and caret, or `None` if the edit doesn't change the text or would exceed `max_chars`
+fn apply_edit(
+    text: &str,
+    caret: usize,
+    selection: Option<Range<usize>>,
+    edit: &actions::TextInputEdit,
+    max_chars: Option<usize>,
+) -> Option<(String, usize)> {
+    use actions::TextInputEdit::*;
+    let mut chars = text.chars().collect::<Vec<_>>();
+    let len = chars.len();
+    let caret = caret.min(len);
+    let (range, inserted) = match (edit, selection) {
+        (Backspace | Delete, Some(selection)) => (selection, vec![]),
+        (Backspace, None) => (caret.saturating_sub(1)..caret, vec![]),
+        (Delete, None) => (caret..(caret + 1).min(len), vec![]),
+        (&Insert(c, true), None) if chars.get(caret).is_some_and(|&next| next != '\n') => (caret..caret + 1, vec![c]),
+        (&Insert(c, _), selection) => (selection.unwrap_or(caret..caret), vec![c]),
+        (Enter, selection) => (selection.unwrap_or(caret..caret), vec!['\n']),
+        (Paste(pasted), selection) => (selection.unwrap_or(caret..caret), pasted.chars().collect()),
+        _ => return None,
+    };
+    let range = range.start.min(len)..range.end.min(len);
+    let new_len = len - range.len() + inserted.len();
+    if (range.is_empty() && inserted.is_empty()) || max_chars.is_some_and(|max_chars| new_len > max_chars && new_len > len) {
+        return None;
+    }
+    let caret = range.start + inserted.len();
+    chars.splice(range, inserted);
+    Some((chars.into_iter().collect(), caret))
+}
+
+// actions which must wait for `bevy_ui_text_input` to apply the ones queued before them
+#[derive(Component, Default)]
+struct DeferredActions(VecDeque<TextInputAction>);
+
+// `bevy_ui_text_input` applies queued actions straight to the buffer, so, before it processes the
+// queues, drop the edits of read only inputs and those rejected by an input's filter, apply the edits
+// of obscured inputs to their real text, whose buffers only hold its masked form, step the inputs' own undo histories rather than
+// `bevy_ui_text_input`'s, and copy, cut, and paste via the `TextInputClipboard` rather than its
+// clipboard
 #[allow(clippy::type_complexity)]
-fn on_change(contents: Query<(Entity, &TextInputContents), (Changed<TextInputContents>, With<ListenToChanges>)>, mut commands: Commands) {
-    for (entity, contents) in contents.iter() {
-        commands.trigger_targets(TextInputChange(contents.get().to_string()), entity);
+fn intercept_text_input_actions(
+    mut inputs: Query<(
+        Entity,
+        &TextInputNode,
+        &mut TextInputBuffer,
+        &mut TextInputQueue,
+        &mut DeferredActions,
+        Option<&mut Obscured>,
+        Option<&TextFilter>,
+        Option<&History>,
+        Option<&ClipboardHooks>,
+        Has<ReadOnly>,
+    )>,
+    mut clipboard: ResMut<TextInputClipboard>,
+    mut submits: EventWriter<TextSubmitEvent>,
+    mut commands: Commands,
+) {
+    for (entity, text_input_node, mut buffer, mut queue, mut deferred, mut obscured_option, filter_option, history_option, hooks_option, read_only) in
+        inputs.iter_mut()
+    {
+        if queue.is_empty() && deferred.0.is_empty() {
+            continue;
+        }
+        let mut actions = mem::take(&mut deferred.0);
+        actions.extend(mem::take(&mut queue.actions));
+        let mut passed = VecDeque::new();
+        while let Some(action) = actions.pop_front() {
+            let action = match action {
+                TextInputAction::Edit(edit @ (actions::TextInputEdit::Undo | actions::TextInputEdit::Redo)) => {
+                    if history_option.is_some_and(|history| history.key_bindings) {
+                        let undo = matches!(edit, actions::TextInputEdit::Undo);
+                        commands.entity(entity).queue(move |entity: EntityWorldMut| step_history(entity, undo));
+                    }
+                    continue;
+                }
+                // obscured inputs can't be copied from, so their real text doesn't leak
+                TextInputAction::Copy | TextInputAction::Cut if obscured_option.is_some() => continue,
+                TextInputAction::Cut if read_only => TextInputAction::Copy,
+                // only follows `bevy_ui_text_input`'s own pastes
+                TextInputAction::PasteDeferred(_) => continue,
+                TextInputAction::Paste if read_only => continue,
+                TextInputAction::Paste => {
+                    let pasted = clipboard.get().map(|text| {
+                        if matches!(text_input_node.mode, TextInputMode::SingleLine) {
+                            text.chars().filter(|&c| c != '\n' && c != '\r').collect()
+                        } else {
+                            text
+                        }
+                    });
+                    let Some(text) = pasted.and_then(|text| run_hooks(hooks_option.map(|hooks| hooks.paste.as_slice()), text)) else { continue };
+                    TextInputAction::Edit(actions::TextInputEdit::Paste(text))
+                }
+                TextInputAction::Edit(edit) if read_only && edits_text(&edit) => continue,
+                action => action,
+            };
+            let applied_here = match &action {
+                TextInputAction::Copy | TextInputAction::Cut => true,
+                TextInputAction::Submit => obscured_option.is_some(),
+                TextInputAction::Edit(edit) => (obscured_option.is_some() || filter_option.is_some()) && edits_text(edit),
+                _ => false,
+            };
+            if !applied_here {
+                passed.push_back(action);
+                continue;
+            }
+            // `bevy_ui_text_input` must apply the actions before this one first
+            if !passed.is_empty() {
+                actions.push_front(action);
+                break;
+            }
+            match action {
+                TextInputAction::Copy | TextInputAction::Cut => {
+                    let copied = buffer
+                        .editor
+                        .copy_selection()
+                        .and_then(|text| run_hooks(hooks_option.map(|hooks| hooks.copy.as_slice()), text));
+                    if let Some(text) = copied {
+                        clipboard.set(text);
+                        if matches!(action, TextInputAction::Cut) {
+                            // removes the selection
+                            actions.push_front(TextInputAction::Edit(actions::TextInputEdit::Delete));
+                        }
+                    }
+                }
+                TextInputAction::Submit => {
+                    let Some(obscured) = obscured_option.as_deref_mut() else { continue };
+                    submits.write(TextSubmitEvent { entity, text: obscured.text.clone() });
+                    if text_input_node.clear_on_submit {
+                        obscured.text.clear();
+                        set_buffer_text(&mut buffer, "");
+                    }
+                }
+                TextInputAction::Edit(edit) => {
+                    let (caret, selection) = caret_and_selection(&buffer);
+                    let passes = |text: &str| filter_option.is_none_or(|TextFilter(filter)| filter(text));
+                    if let Some(obscured) = obscured_option.as_deref_mut() {
+                        if let Some((text, caret)) = apply_edit(&obscured.text, caret, selection, &edit, text_input_node.max_chars)
+                            && passes(&text)
+                            && passes_input_filter(text_input_node.filter, &text)
+                        {
+                            obscured.text = text;
+                            set_buffer_text(&mut buffer, &obscured.shown());
+                            set_selection(&mut buffer, Some(caret..caret));
+                        }
+                        continue;
+                    }
+                    // pass on only the edits whose text passes the filter, for `bevy_ui_text_input` to
+                    // apply; those it can't predict, i.e. indents, are dropped
+                    let edited = apply_edit(&buffer.get_text(), caret, selection, &edit, text_input_node.max_chars);
+                    if edited.is_some_and(|(text, _)| passes(&text)) {
+                        passed.push_back(TextInputAction::Edit(edit));
+                    }
+                }
+                _ => (),
+            }
+        }
+        deferred.0 = actions;
+        queue.actions = passed;
+    }
+}
+
+// the filter of an input's text, checked against the result of each edit before it is applied
+#[derive(Component)]
+struct TextFilter(Box<dyn Fn(&str) -> bool + Send + Sync>);
+
+// `bevy_ui_text_input` only checks its filter against the edits it applies itself, which excludes
+// those of obscured inputs
+fn passes_input_filter(filter_option: Option<TextInputFilter>, text: &str) -> bool {
+    match filter_option {
+        None => true,
+        Some(TextInputFilter::Hex) => text.chars().all(|c| c.is_ascii_hexdigit()),
+        Some(filter) => filter.regex().is_none_or(|regex| regex.is_match(text)),
+    }
+}
+
+/// Common filters and validators for [`TextInput`]s, see [`TextInput::preset`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextInputPreset {
+    /// An optionally negative whole number.
+    Integer,
+    /// An optionally negative decimal number with at most `precision` digits after the decimal point.
+    Decimal {
+        #[allow(missing_docs)]
+        precision: u32,
+    },
+    /// A hex color with 3, 4, 6, or 8 digits and an optional leading `#`.
+    HexColor,
+    /// A letter or underscore followed by letters, digits, or underscores.
+    Identifier,
+}
+
+impl TextInputPreset {
+    // `bevy_ui_text_input`'s filter for the numeric presets
+    fn input_filter(self) -> Option<TextInputFilter> {
+        match self {
+            Self::Integer => Some(TextInputFilter::Integer),
+            Self::Decimal { .. } => Some(TextInputFilter::Decimal),
+            Self::HexColor | Self::Identifier => None,
+        }
+    }
+
+    // whether the text could be completed into a valid one, beyond what the `input_filter` checks,
+    // so edits along the way aren't rejected
+    fn partial_match_filter(self) -> Option<TextFilter> {
+        let filter: Box<dyn Fn(&str) -> bool + Send + Sync> = match self {
+            Self::Integer => return None,
+            Self::Decimal { precision } => Box::new(move |text| {
+                text.split_once('.').is_none_or(|(_, fraction)| precision > 0 && fraction.len() as u32 <= precision)
+            }),
+            Self::HexColor => Box::new(|text| {
+                let digits = text.strip_prefix('#').unwrap_or(text);
+                digits.len() <= 8 && digits.chars().all(|c| c.is_ascii_hexdigit())
+            }),
+            Self::Identifier => Box::new(|text| {
+                let mut chars = text.chars();
+                chars.next().is_none_or(|c| c.is_alphabetic() || c == '_') && chars.all(|c| c.is_alphanumeric() || c == '_')
+            }),
+        };
+        Some(TextFilter(filter))
+    }
+
+    fn validate(self, text: &str) -> Result<(), String> {
+        let valid = match self {
+            Self::Integer => text.parse::<i64>().is_ok(),
+            Self::Decimal { .. } => text.chars().any(|c| c.is_ascii_digit()) && text.parse::<f64>().is_ok(),
+            Self::HexColor => Srgba::hex(text).is_ok(),
+            Self::Identifier => !text.is_empty(),
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(match self {
+                Self::Integer => "expected a whole number".to_string(),
+                Self::Decimal { precision } => format!("expected a number with at most {precision} decimal places"),
+                Self::HexColor => "expected a hex color like #ff8800".to_string(),
+                Self::Identifier => "expected a letter or underscore followed by letters, digits, or underscores".to_string(),
+            })
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+#[derive(Component, Default)]
+struct Validators(Vec<Box<dyn Fn(&str) -> Result<(), String> + Send + Sync>>);
+
+#[derive(Component)]
+struct ValidationError(Mutable<Option<String>>);
+
+#[allow(clippy::type_complexity)]
+fn validate(
+    inputs: Query<
+        (&TextInputContents, &Validators, &ValidationError, Option<&Obscured>),
+        Or<(Changed<TextInputContents>, Changed<Validators>, Changed<Obscured>)>,
+    >,
+) {
+    for (contents, validators, ValidationError(error), obscured_option) in inputs.iter() {
+        let text = obscured_option.map(|obscured| obscured.text.as_str()).unwrap_or(contents.get());
+        error.set_neq(validators.0.iter().find_map(|validator| validator(text).err()));
+    }
+}
+
+#[derive(Component)]
+struct ListenToSubmits;
+
+#[derive(Event)]
+struct TextInputSubmit(String);
+
+// obscured inputs submit their real text, see `intercept_text_input_actions`
+fn on_submit(mut submits: EventReader<TextSubmitEvent>, listeners: Query<(), With<ListenToSubmits>>, mut commands: Commands) {
+    for TextSubmitEvent { entity, text } in submits.read() {
+        if listeners.contains(*entity) {
+            commands.trigger_targets(TextInputSubmit(text.clone()), *entity);
+        }
+    }
+}
+
+#[derive(Component)]
+struct ListenToCancels;
+
+#[derive(Event)]
+struct TextInputCancel;
+
+fn on_cancel(
+    keys: Res<ButtonInput<KeyCode>>,
+    focused_option: Res<InputFocus>,
+    listeners: Query<(), With<ListenToCancels>>,
+    mut commands: Commands,
+) {
+    if keys.just_pressed(KeyCode::Escape) && let Some(entity) = focused_option.0 && listeners.contains(entity) {
+        commands.trigger_targets(TextInputCancel, entity);
     }
 }
 
@@ -336,13 +1561,196 @@ fn clear_selection_on_focus_change(
 pub(super) fn plugin(app: &mut App) {
     app
     .add_plugins(TextInputPlugin)
+    .init_resource::<TextInputClipboard>()
+    .add_systems(
+        PostUpdate,
+        intercept_text_input_actions
+            .after(edit::mouse_wheel_scroll)
+            .before(edit::process_text_input_queues),
+    )
     .add_systems(
         Update,
         (
-            on_change.run_if(any_with_component::<ListenToChanges>),
+            (record_history, on_change.run_if(any_with_component::<ListenToChanges>)).chain(),
+            reveal_obscured_on_hold.run_if(any_with_component::<Obscured>),
+            sync_selection_states,
+            on_submit.run_if(any_with_component::<ListenToSubmits>),
+            validate.run_if(any_with_component::<Validators>),
+            on_cancel.run_if(any_with_component::<ListenToCancels>),
+            sync_placeholders.run_if(any_with_component::<Placeholder>),
+            animate_floating_labels.run_if(any_with_component::<FloatingLabelText>),
             on_focus_changed.run_if(resource_changed_or_removed::<InputFocus>),
             clear_selection_on_focus_change.run_if(not(resource_exists::<ClearSelectionOnFocusChangeDisabled>))
         )
             .run_if(any_with_component::<TextInputNode>),
     );
+    #[cfg(not(target_os = "macos"))]
+    app.add_observer(redo_on_ctrl_shift_z);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actions::TextInputEdit::*;
+    use crate::clipboard::{Clipboard, InMemoryClipboard};
+    use bevy_ecs::system::RunSystemOnce;
+
+    fn edited(text: &str, caret: usize) -> Option<(String, usize)> {
+        Some((text.to_string(), caret))
+    }
+
+    #[test]
+    fn obscured_edits_apply_to_the_real_text() {
+        // typed and pasted mask characters are kept like any other
+        assert_eq!(apply_edit("ab", 1, None, &Insert('•', false), None), edited("a•b", 2));
+        assert_eq!(apply_edit("abcd", 3, Some(1..3), &Paste("•x".to_string()), None), edited("a•xd", 3));
+        assert_eq!(apply_edit("abc", 1, None, &Delete, None), edited("ac", 1));
+        assert_eq!(apply_edit("abc", 1, None, &Insert('x', true), None), edited("axc", 2));
+        assert_eq!(apply_edit("abc", 0, None, &Backspace, None), None);
+        assert_eq!(apply_edit("abc", 3, None, &Insert('x', false), Some(3)), None);
+        assert_eq!(apply_edit("abc", 3, Some(0..3), &Insert('x', false), Some(3)), edited("x", 1));
+    }
+
+    fn intercepting_world(clipboard: InMemoryClipboard) -> World {
+        let mut world = World::new();
+        world.init_resource::<Events<TextSubmitEvent>>();
+        world.insert_resource(TextInputClipboard::new(clipboard));
+        world
+    }
+
+    fn input(world: &mut World, text: &str) -> Entity {
+        let entity = world.spawn((TextInputNode::default(), DeferredActions::default())).id();
+        set_text(&mut world.entity_mut(entity), text.to_string());
+        entity
+    }
+
+    fn type_char(world: &mut World, entity: Entity, c: char) {
+        world.get_mut::<TextInputQueue>(entity).unwrap().add(TextInputAction::Edit(Insert(c, false)));
+        world.run_system_once(intercept_text_input_actions).unwrap();
+    }
+
+    #[test]
+    fn read_only_and_obscured_inputs_intercept_edits() {
+        let mut world = intercepting_world(InMemoryClipboard::default());
+
+        let read_only = input(&mut world, "abc");
+        world.entity_mut(read_only).insert(ReadOnly);
+        type_char(&mut world, read_only, 'x');
+        assert!(world.get::<TextInputQueue>(read_only).unwrap().is_empty());
+        assert_eq!(world.get::<TextInputBuffer>(read_only).unwrap().get_text(), "abc");
+
+        let obscured = input(&mut world, "abc");
+        world.entity_mut(obscured).insert(Obscured {
+            settings: ObscuredSettings::default(),
+            text: "abc".to_string(),
+            revealed: false,
+        });
+        type_char(&mut world, obscured, '•');
+        assert!(world.get::<TextInputQueue>(obscured).unwrap().is_empty());
+        assert_eq!(world.get::<Obscured>(obscured).unwrap().text, "abc•");
+        // the real text never reaches the buffer
+        assert_eq!(world.get::<TextInputBuffer>(obscured).unwrap().get_text(), "••••");
+    }
+
+    #[test]
+    fn filtered_edits_never_reach_the_buffer() {
+        let mut world = intercepting_world(InMemoryClipboard::default());
+
+        let entity = input(&mut world, "4.2");
+        world.entity_mut(entity).insert(TextInputPreset::Decimal { precision: 1 }.partial_match_filter().unwrap());
+        type_char(&mut world, entity, '5');
+        assert!(world.get::<TextInputQueue>(entity).unwrap().is_empty());
+        world.get_mut::<TextInputQueue>(entity).unwrap().add(TextInputAction::Edit(Backspace));
+        world.run_system_once(intercept_text_input_actions).unwrap();
+        // passed on for `bevy_ui_text_input` to apply
+        assert_eq!(world.get::<TextInputQueue>(entity).unwrap().actions.len(), 1);
+
+        // obscured inputs check `bevy_ui_text_input`'s filter themselves
+        let obscured = input(&mut world, "12");
+        world.get_mut::<TextInputNode>(obscured).unwrap().filter = TextInputPreset::Integer.input_filter();
+        world.entity_mut(obscured).insert(Obscured {
+            settings: ObscuredSettings::default(),
+            text: "12".to_string(),
+            revealed: false,
+        });
+        type_char(&mut world, obscured, 'x');
+        assert_eq!(world.get::<Obscured>(obscured).unwrap().text, "12");
+        type_char(&mut world, obscured, '3');
+        assert_eq!(world.get::<Obscured>(obscured).unwrap().text, "123");
+    }
+
+    #[test]
+    fn undo_steps_the_inputs_own_history() {
+        let mut world = intercepting_world(InMemoryClipboard::default());
+        let undo = |world: &mut World, entity| {
+            world.get_mut::<TextInputQueue>(entity).unwrap().add(TextInputAction::Edit(Undo));
+            world.run_system_once(intercept_text_input_actions).unwrap();
+        };
+
+        let entity = input(&mut world, "abc");
+        let mut history = History::new(Mutable::new(false), Mutable::new(false));
+        history.undo.push(Snapshot { text: "ab".to_string(), caret: 2 });
+        history.current = Snapshot { text: "abc".to_string(), caret: 3 };
+        world.entity_mut(entity).insert(history);
+
+        world.entity_mut(entity).insert(ReadOnly);
+        undo(&mut world, entity);
+        assert_eq!(world.get::<TextInputBuffer>(entity).unwrap().get_text(), "abc");
+
+        world.entity_mut(entity).remove::<ReadOnly>();
+        undo(&mut world, entity);
+        // `bevy_ui_text_input` never sees the undo
+        assert!(world.get::<TextInputQueue>(entity).unwrap().is_empty());
+        assert_eq!(world.get::<TextInputBuffer>(entity).unwrap().get_text(), "ab");
+        assert_eq!(world.get::<History>(entity).unwrap().redo.len(), 1);
+    }
+
+    #[test]
+    fn clipboard_shortcuts_use_the_text_input_clipboard() {
+        let mut clipboard = InMemoryClipboard::default();
+        let mut world = intercepting_world(clipboard.clone());
+        let run = |world: &mut World, entity, action| {
+            world.get_mut::<TextInputQueue>(entity).unwrap().add(action);
+            world.run_system_once(intercept_text_input_actions).unwrap();
+            mem::take(&mut world.get_mut::<TextInputQueue>(entity).unwrap().actions)
+        };
+
+        let entity = input(&mut world, "abc");
+        world.get_mut::<TextInputNode>(entity).unwrap().mode = TextInputMode::SingleLine;
+        set_selection(&mut world.get_mut::<TextInputBuffer>(entity).unwrap(), Some(0..2));
+        assert!(run(&mut world, entity, TextInputAction::Copy).is_empty());
+        assert_eq!(clipboard.get().as_deref(), Some("ab"));
+
+        clipboard.set("x\ny".to_string());
+        let queued = run(&mut world, entity, TextInputAction::Paste);
+        // newlines are stripped from text pasted into single line inputs
+        assert!(matches!(queued.front(), Some(TextInputAction::Edit(Paste(text))) if text == "xy"));
+
+        set_selection(&mut world.get_mut::<TextInputBuffer>(entity).unwrap(), Some(1..3));
+        let queued = run(&mut world, entity, TextInputAction::Cut);
+        assert_eq!(clipboard.get().as_deref(), Some("bc"));
+        assert!(matches!(queued.front(), Some(TextInputAction::Edit(Delete))));
+
+        world.entity_mut(entity).insert(ReadOnly);
+        assert!(run(&mut world, entity, TextInputAction::Cut).is_empty());
+        assert!(run(&mut world, entity, TextInputAction::Paste).is_empty());
+    }
+
+    #[test]
+    fn placeholder_color_restores_the_prompt_color() {
+        let mut world = World::new();
+        world.init_resource::<InputFocus>();
+        let prompt = TextInputPrompt::new("");
+        let prompt_color = prompt.color;
+        let red = Color::srgb(1., 0., 0.);
+        let entity = world
+            .spawn((TextInputNode::default(), TextInputContents::default(), prompt, Placeholder { color: Some(red), ..default() }))
+            .id();
+        world.run_system_once(sync_placeholders).unwrap();
+        assert_eq!(world.get::<TextInputPrompt>(entity).unwrap().color, Some(red));
+
+        world.get_mut::<Placeholder>(entity).unwrap().color = None;
+        world.run_system_once(sync_placeholders).unwrap();
+        assert_eq!(world.get::<TextInputPrompt>(entity).unwrap().color, prompt_color);
+    }
 }
@@ -0,0 +1,243 @@
+//! Semantics for showing a tooltip [`Element`](super::element::Element) while another
+//! [`Element`](super::element::Element) is hovered, see [`Tooltipable`].
+
+use std::{
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_picking::pointer::{PointerId, PointerLocation, PointerMap};
+use bevy_render::prelude::*;
+use bevy_ui::{ComputedNodeTarget, UiSystem, prelude::*};
+use futures_signals::signal::{Mutable, Signal, SignalExt, always};
+
+use super::{
+    el::El,
+    element::IntoOptionElement,
+    pointer_event_aware::{ElementSpace, PickingIgnoredTree, PointerEventAware},
+    raw::{RawElWrapper, RawElement},
+    utils::{clone, sleep},
+};
+
+/// [`GlobalZIndex`] of tooltips, so they are rendered above everything except dragged elements.
+pub const TOOLTIP_Z_INDEX: i32 = i32::MAX - 2;
+
+/// Where a tooltip is placed relative to the [`Element`](super::element::Element) it describes.
+/// Tooltips that would overflow the window are flipped to the opposite side and then clamped to
+/// the window's bounds.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub enum TooltipPlacement {
+    /// Above the element, centered horizontally.
+    Top,
+    /// Below the element, centered horizontally.
+    #[default]
+    Bottom,
+    /// Left of the element, centered vertically.
+    Left,
+    /// Right of the element, centered vertically.
+    Right,
+    /// Below and to the right of the pointer, following it while it moves over the element.
+    FollowCursor,
+}
+
+/// [`Component`] configuring how a [`Tooltipable`] element's tooltip is shown.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct TooltipSettings {
+    /// How long the element must be hovered before its tooltip is shown.
+    pub show_delay: Duration,
+    /// How long the tooltip stays shown after the element is no longer hovered.
+    pub hide_delay: Duration,
+    /// See [`TooltipPlacement`].
+    pub placement: TooltipPlacement,
+    /// Distance between the tooltip and the element, or the pointer, in logical pixels.
+    pub offset: f32,
+}
+
+impl Default for TooltipSettings {
+    fn default() -> Self {
+        Self {
+            show_delay: Duration::from_millis(500),
+            hide_delay: Duration::ZERO,
+            placement: TooltipPlacement::default(),
+            offset: 8.,
+        }
+    }
+}
+
+/// Points from a tooltip to the element it describes.
+#[derive(Component)]
+struct TooltipOf(Entity);
+
+/// Enables showing a tooltip [`Element`](super::element::Element) after an
+/// [`Element`](super::element::Element) is hovered for a while. Tooltips are spawned at the root
+/// of the UI with a [`GlobalZIndex`] of [`TOOLTIP_Z_INDEX`], so they are not clipped by scroll
+/// containers, and they do not block picking.
+pub trait Tooltipable: PointerEventAware {
+    /// Show the `tooltip` while this element is hovered, see [`TooltipSettings`].
+    fn tooltip<IOE: IntoOptionElement + Send + 'static>(self, tooltip_option: IOE) -> Self {
+        self.tooltip_signal(always(tooltip_option))
+    }
+
+    /// Reactively set the tooltip shown while this element is hovered. When the [`Signal`] outputs
+    /// [`None`], no tooltip is shown.
+    fn tooltip_signal<IOE: IntoOptionElement + 'static, S: Signal<Item = IOE> + Send + 'static>(
+        mut self,
+        tooltip_option_signal_option: impl Into<Option<S>>,
+    ) -> Self {
+        if let Some(tooltip_option_signal) = tooltip_option_signal_option.into() {
+            // whether this element is hovered, and how long to wait before acting on it
+            let hover = Mutable::new((false, Duration::ZERO));
+            let shown = hover
+                .signal()
+                .map_future(|(hovered, delay)| async move {
+                    if !delay.is_zero() {
+                        sleep(delay).await;
+                    }
+                    hovered
+                })
+                .map(|shown_option| shown_option.unwrap_or(false))
+                .dedupe();
+            let tooltip_holder = Arc::new(OnceLock::new());
+            self = self
+                .on_hovered_change_with_system(clone!((hover) move |In((entity, hovered)): In<(Entity, bool)>, settings: Query<&TooltipSettings>| {
+                    let settings = settings.get(entity).copied().unwrap_or_default();
+                    hover.set((hovered, if hovered { settings.show_delay } else { settings.hide_delay }));
+                }))
+                .update_raw_el(|raw_el| {
+                    raw_el
+                        .on_spawn(clone!((tooltip_holder) move |world, entity| {
+                            let tooltip = El::<Node>::new()
+                                .with_node(|mut node| node.position_type = PositionType::Absolute)
+                                .global_z_index(GlobalZIndex(TOOLTIP_Z_INDEX))
+                                .update_raw_el(|raw_el| raw_el.insert(PickingIgnoredTree))
+                                .visibility(Visibility::Hidden)
+                                .visibility_signal(shown.map(|shown| if shown { Visibility::Visible } else { Visibility::Hidden }))
+                                .update_raw_el(move |raw_el| raw_el.insert(TooltipOf(entity)))
+                                .child_signal(tooltip_option_signal)
+                                .into_raw()
+                                .into_node_builder()
+                                .spawn(world);
+                            let _ = tooltip_holder.set(tooltip);
+                        }))
+                        .on_remove(move |world, _| {
+                            if let Some(&tooltip) = tooltip_holder.get() {
+                                world.commands().queue(move |world: &mut World| {
+                                    let _ = world.try_despawn(tooltip);
+                                })
+                            }
+                        })
+                });
+        }
+        self
+    }
+
+    /// Configure how this element's tooltip is shown, see [`TooltipSettings`].
+    fn tooltip_settings(mut self, settings_option: impl Into<Option<TooltipSettings>>) -> Self {
+        if let Some(settings) = settings_option.into() {
+            self = self.update_raw_el(|raw_el| raw_el.insert(settings));
+        }
+        self
+    }
+}
+
+// place `size` at `preferred` along one axis, flipping to `flipped` if it would overflow the
+// viewport, and clamping it to the viewport's bounds if both do
+fn place(preferred: f32, flipped: f32, size: f32, viewport: f32) -> f32 {
+    let fits = |start: f32| start >= 0. && start + size <= viewport;
+    let start = if fits(preferred) || !fits(flipped) {
+        preferred
+    } else {
+        flipped
+    };
+    start.clamp(0., (viewport - size).max(0.))
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn position_tooltips(
+    mut tooltips: Query<(Entity, &TooltipOf, &ComputedNode, &Visibility, &mut Node, Option<&UiTargetCamera>)>,
+    settings: Query<&TooltipSettings>,
+    targets: Query<&ComputedNodeTarget>,
+    element_space: ElementSpace,
+    pointer_map: Res<PointerMap>,
+    pointers: Query<&PointerLocation>,
+    ui_scale: Res<UiScale>,
+    mut commands: Commands,
+) {
+    let pointer = pointer_map
+        .get_entity(PointerId::Mouse)
+        .and_then(|entity| pointers.get(entity).ok())
+        .and_then(|pointer| pointer.location.as_ref())
+        .map(|location| location.position / ui_scale.0);
+    for (entity, &TooltipOf(owner), computed_node, visibility, mut node, target_camera_option) in tooltips.iter_mut() {
+        // render tooltips with the camera of the element they describe, which is where they are
+        // placed relative to
+        let Ok(target) = targets.get(owner) else {
+            continue;
+        };
+        if let Some(camera) = target.camera()
+            && target_camera_option.map(UiTargetCamera::entity) != Some(camera)
+        {
+            commands.entity(entity).try_insert(UiTargetCamera(camera));
+        }
+        if matches!(visibility, Visibility::Hidden) {
+            continue;
+        }
+        let Some(rect) = element_space.rect(owner) else {
+            continue;
+        };
+        let viewport = target.logical_size();
+        let TooltipSettings { placement, offset, .. } = settings.get(owner).copied().unwrap_or_default();
+        let size = computed_node.size() * computed_node.inverse_scale_factor();
+        let centered = rect.center() - size / 2.;
+        let (x, y) = match placement {
+            TooltipPlacement::Top | TooltipPlacement::Bottom => {
+                let below = rect.max.y + offset;
+                let above = rect.min.y - offset - size.y;
+                let (preferred, flipped) = if placement == TooltipPlacement::Top {
+                    (above, below)
+                } else {
+                    (below, above)
+                };
+                (
+                    place(centered.x, centered.x, size.x, viewport.x),
+                    place(preferred, flipped, size.y, viewport.y),
+                )
+            }
+            TooltipPlacement::Left | TooltipPlacement::Right => {
+                let right = rect.max.x + offset;
+                let left = rect.min.x - offset - size.x;
+                let (preferred, flipped) = if placement == TooltipPlacement::Left {
+                    (left, right)
+                } else {
+                    (right, left)
+                };
+                (
+                    place(preferred, flipped, size.x, viewport.x),
+                    place(centered.y, centered.y, size.y, viewport.y),
+                )
+            }
+            TooltipPlacement::FollowCursor => {
+                let Some(pointer) = pointer else {
+                    continue;
+                };
+                (
+                    place(pointer.x + offset, pointer.x - offset - size.x, size.x, viewport.x),
+                    place(pointer.y + offset, pointer.y - offset - size.y, size.y, viewport.y),
+                )
+            }
+        };
+        node.left = Val::Px(x);
+        node.top = Val::Px(y);
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        PostUpdate,
+        position_tooltips
+            .before(UiSystem::Layout)
+            .run_if(any_with_component::<TooltipOf>),
+    );
+}
@@ -7,16 +7,17 @@ use std::{
 };
 
 use super::{
+    mouse_wheel_scrollable::{ScrollMode, apply_overscroll, max_scroll_offset, scroll_to_target},
     raw::{RawElWrapper, observe, register_system, utils::remove_system_holder_on_remove},
-    utils::clone,
+    utils::{clone, spawn},
 };
 use apply::Apply;
 use bevy_app::prelude::*;
 use bevy_ecs::{prelude::*, system::SystemParam};
 use bevy_math::prelude::*;
-use bevy_transform::prelude::*;
-use bevy_ui::prelude::*;
-use futures_signals::signal::{Mutable, Signal};
+use bevy_transform::{TransformSystem, prelude::*};
+use bevy_ui::{UiSystem, prelude::*};
+use futures_signals::signal::{Mutable, Signal, SignalExt};
 
 /// Dimensions of an element's "scene", which contains both its visible (via its [`Viewport`]) and
 /// hidden parts.
@@ -58,6 +59,7 @@ pub struct MutableViewport {
 pub struct OnViewportLocationChange;
 
 /// Along which axes the [`Viewport`] can be mutated.
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum Axis {
     #[allow(missing_docs)]
     Horizontal,
@@ -67,6 +69,209 @@ pub enum Axis {
     Both,
 }
 
+// offsets, in logical pixels, closer than this to a limit are considered at it
+const SCROLL_LIMIT_EPSILON: f32 = 0.5;
+
+/// How far a viewport is scrolled along one axis, see [`ScrollMetrics`].
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct AxisScrollMetrics {
+    /// How far the viewport is scrolled, from `0.` at the start to `1.` at the end; `0.` if the
+    /// viewport can't scroll.
+    pub progress: f32,
+    /// Whether the viewport is scrolled to its start.
+    pub at_start: bool,
+    /// Whether the viewport is scrolled to its end.
+    pub at_end: bool,
+    /// Whether the contents overflow the viewport, i.e. whether there is anything to scroll.
+    pub can_scroll: bool,
+}
+
+impl AxisScrollMetrics {
+    fn new(position: f32, max: f32) -> Self {
+        let can_scroll = max > SCROLL_LIMIT_EPSILON;
+        let position = position.clamp(0., max);
+        Self {
+            progress: if can_scroll { position / max } else { 0. },
+            at_start: position <= SCROLL_LIMIT_EPSILON,
+            at_end: position >= max - SCROLL_LIMIT_EPSILON,
+            can_scroll,
+        }
+    }
+}
+
+/// How far a viewport is scrolled along each axis, derived from its [`ScrollPosition`] and the
+/// size of its contents; useful for driving fading edges or "scroll for more" hints.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct ScrollMetrics {
+    #[allow(missing_docs)]
+    pub x: AxisScrollMetrics,
+    #[allow(missing_docs)]
+    pub y: AxisScrollMetrics,
+}
+
+#[derive(Default)]
+struct NearEndState {
+    fired: bool,
+    max: f32,
+}
+
+/// Where an element is placed within its viewport when it is scrolled into view.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub enum ScrollAlignment {
+    /// Scroll as little as possible to fully reveal the element, not scrolling at all if it is
+    /// already visible.
+    #[default]
+    Nearest,
+    /// Align the start of the element with the start of the viewport.
+    Start,
+    /// Align the center of the element with the center of the viewport.
+    Center,
+    /// Align the end of the element with the end of the viewport.
+    End,
+}
+
+/// Configures how an element is scrolled into view, like the web's `ScrollIntoViewOptions`.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct ScrollIntoViewOptions {
+    /// See [`ScrollAlignment`].
+    pub alignment: ScrollAlignment,
+    /// Whether to jump or animate to the new offset, see [`ScrollMode`].
+    pub mode: ScrollMode,
+}
+
+#[derive(Clone, Copy)]
+enum ScrollTarget {
+    Offset(Vec2),
+    Child(Entity, ScrollAlignment),
+}
+
+/// Scroll request which is resolved once layout has computed the rects it depends on.
+#[derive(Component, Clone, Copy)]
+struct PendingScroll {
+    target: ScrollTarget,
+    mode: ScrollMode,
+    // frames spent waiting for layout to give the viewport and child non-empty rects
+    frames: u32,
+}
+
+// pending scrolls whose rects are still empty after this many frames, e.g. those targeting a child
+// which is hidden or was despawned, are dropped
+const PENDING_SCROLL_FRAMES: u32 = 10;
+
+/// Programmatic scrolling of a [`MutableViewport`] [`Entity`]. Requests are applied once layout has
+/// given the viewport, and any child they target, a non-empty rect, so they can target children
+/// which were just spawned; a later request replaces an earlier one that has not been applied yet.
+pub trait ViewportCommands {
+    /// Scroll the viewport to `offset`, in logical pixels, clamped to the scrollable range; e.g.
+    /// `Vec2::new(0., f32::MAX)` scrolls to the bottom.
+    fn scroll_to(&mut self, offset: Vec2, mode: ScrollMode) -> &mut Self;
+
+    /// Scroll the viewport to reveal `child`, which can be any descendant, see
+    /// [`ScrollIntoViewOptions`].
+    fn scroll_into_view(&mut self, child: Entity, options: ScrollIntoViewOptions) -> &mut Self;
+}
+
+impl ViewportCommands for EntityCommands<'_> {
+    fn scroll_to(&mut self, offset: Vec2, mode: ScrollMode) -> &mut Self {
+        self.try_insert(PendingScroll {
+            target: ScrollTarget::Offset(offset),
+            mode,
+            frames: 0,
+        })
+    }
+
+    fn scroll_into_view(
+        &mut self,
+        child: Entity,
+        ScrollIntoViewOptions { alignment, mode }: ScrollIntoViewOptions,
+    ) -> &mut Self {
+        self.try_insert(PendingScroll {
+            target: ScrollTarget::Child(child, alignment),
+            mode,
+            frames: 0,
+        })
+    }
+}
+
+/// Enables an element to scroll its nearest [`MutableViewport`] ancestor to reveal itself.
+pub trait ScrollIntoViewable: RawElWrapper {
+    /// When the [`Signal`] outputs `true`, scroll this element's nearest [`MutableViewport`]
+    /// ancestor to reveal it, once layout has computed its rect, see [`ScrollIntoViewOptions`].
+    fn scroll_into_view_when<S: Signal<Item = bool> + Send + 'static>(
+        mut self,
+        when_signal_option: impl Into<Option<S>>,
+        options: ScrollIntoViewOptions,
+    ) -> Self {
+        if let Some(when_signal) = when_signal_option.into() {
+            self = self.update_raw_el(|raw_el| {
+                raw_el.on_signal_with_system(
+                    when_signal.dedupe(),
+                    move |In((entity, when)): In<(Entity, bool)>,
+                          child_ofs: Query<&ChildOf>,
+                          mutable_viewports: Query<(), With<MutableViewport>>,
+                          mut commands: Commands| {
+                        if when
+                            && let Some(viewport) = child_ofs
+                                .iter_ancestors(entity)
+                                .find(|&ancestor| mutable_viewports.contains(ancestor))
+                            && let Ok(mut viewport) = commands.get_entity(viewport)
+                        {
+                            viewport.scroll_into_view(entity, options);
+                        }
+                    },
+                )
+            });
+        }
+        self
+    }
+}
+
+/// Which edge of its nearest [`MutableViewport`] ancestor a [`Stickable`] element is pinned to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StickyEdge {
+    #[allow(missing_docs)]
+    Top,
+    #[allow(missing_docs)]
+    Bottom,
+    #[allow(missing_docs)]
+    Left,
+    #[allow(missing_docs)]
+    Right,
+}
+
+impl StickyEdge {
+    fn is_vertical(self) -> bool {
+        matches!(self, Self::Top | Self::Bottom)
+    }
+
+    fn is_start(self) -> bool {
+        matches!(self, Self::Top | Self::Left)
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+struct Sticky(StickyEdge);
+
+/// Enables an element to stick to an edge of its nearest [`MutableViewport`] ancestor while it is
+/// scrolled, like CSS's `position: sticky`.
+pub trait Stickable: RawElWrapper {
+    /// Pin this element to `edge` of its nearest [`MutableViewport`] ancestor once it would
+    /// otherwise scroll past it, e.g. for section headers of grouped lists. The element never
+    /// leaves its parent, so a header placed in a column with its section's items scrolls away with
+    /// the last of them, and it is pushed out by the next sibling pinned to the same edge, so headers
+    /// placed directly in the viewport replace each other. Elements with the default [`ZIndex`] are
+    /// given a [`ZIndex`] of `1` so they are drawn over the contents scrolling beneath them.
+    fn sticky(self, edge: StickyEdge) -> Self {
+        self.update_raw_el(|raw_el| {
+            raw_el.insert(Sticky(edge)).with_component::<ZIndex>(|mut z_index| {
+                if z_index.0 == 0 {
+                    z_index.0 = 1;
+                }
+            })
+        })
+    }
+}
+
 /// Sentinel component to store the last scroll position set by a signal.
 /// This is used to break feedback loops in two-way bindings.
 #[derive(Component, Default, Debug)]
@@ -125,6 +330,141 @@ pub trait ViewportMutable: RawElWrapper {
         self.on_viewport_location_change_with_system(move |In((_, (scene, viewport)))| handler(scene, viewport))
     }
 
+    /// When this element's [`ScrollMetrics`] change, run a [`System`] which takes
+    /// [`In`](`System::In`) this element's [`Entity`] and [`ScrollMetrics`]. This method can be
+    /// called repeatedly to register many such handlers.
+    fn on_scroll_metrics_change_with_system<Marker>(
+        self,
+        handler: impl IntoSystem<In<(Entity, ScrollMetrics)>, (), Marker> + Send + 'static,
+    ) -> Self {
+        self.update_raw_el(|raw_el| {
+            let system_holder = Arc::new(OnceLock::new());
+            raw_el
+            .insert(OnViewportLocationChange)
+            .on_spawn(clone!((system_holder) move |world, entity| {
+                let system = register_system(world, handler);
+                let _ = system_holder.set(system);
+                observe(world, entity, move |_: Trigger<MutableViewport>, viewports: Query<(&ScrollPosition, &ComputedNode)>, mut last_metrics: Local<Option<ScrollMetrics>>, mut commands: Commands| {
+                    if let Ok((&ScrollPosition { offset_x, offset_y }, computed_node)) = viewports.get(entity) {
+                        let max = max_scroll_offset(computed_node);
+                        let metrics = ScrollMetrics {
+                            x: AxisScrollMetrics::new(offset_x, max.x),
+                            y: AxisScrollMetrics::new(offset_y, max.y),
+                        };
+                        if *last_metrics != Some(metrics) {
+                            *last_metrics = Some(metrics);
+                            commands.run_system_with(system, (entity, metrics));
+                        }
+                    }
+                });
+            }))
+            .apply(remove_system_holder_on_remove(system_holder))
+        })
+    }
+
+    /// When this element's [`ScrollMetrics`] change, run a function with its [`ScrollMetrics`].
+    /// This method can be called repeatedly to register many such handlers.
+    fn on_scroll_metrics_change(self, mut handler: impl FnMut(ScrollMetrics) + Send + Sync + 'static) -> Self {
+        self.on_scroll_metrics_change_with_system(move |In((_, metrics))| handler(metrics))
+    }
+
+    /// Sync a [`Mutable<ScrollMetrics>`] with this element's [`ScrollMetrics`], from which signals
+    /// like `metrics.signal().map(|metrics| metrics.y.at_end).dedupe()` can be derived.
+    fn scroll_metrics_sync(self, metrics: Mutable<ScrollMetrics>) -> Self {
+        self.on_scroll_metrics_change(move |new| metrics.set_neq(new))
+    }
+
+    /// When this element's viewport comes within `threshold` logical pixels of the end of its
+    /// contents, run a [`System`] which takes [`In`](`System::In`) this element's [`Entity`]; the end
+    /// is vertical unless the element only scrolls horizontally. The handler runs once per crossing
+    /// of the threshold, re-arming when the viewport moves back past it or when the size of the
+    /// contents changes, e.g. once another page has been appended. This method can be called
+    /// repeatedly to register many such handlers.
+    fn on_scroll_near_end_with_system<Marker>(
+        self,
+        threshold: f32,
+        handler: impl IntoSystem<In<Entity>, (), Marker> + Send + 'static,
+    ) -> Self {
+        self.update_raw_el(|raw_el| {
+            let system_holder = Arc::new(OnceLock::new());
+            raw_el
+            .insert(OnViewportLocationChange)
+            .on_spawn(clone!((system_holder) move |world, entity| {
+                let system = register_system(world, handler);
+                let _ = system_holder.set(system);
+                observe(world, entity, move |_: Trigger<MutableViewport>, viewports: Query<(&ScrollPosition, &ComputedNode, &Node)>, mut state: Local<NearEndState>, mut commands: Commands| {
+                    if let Ok((&ScrollPosition { offset_x, offset_y }, computed_node, node)) = viewports.get(entity) {
+                        // not laid out yet
+                        if computed_node.size() == Vec2::ZERO {
+                            return;
+                        }
+                        let max = max_scroll_offset(computed_node);
+                        let (max, offset) = if node.overflow.y != OverflowAxis::Scroll && node.overflow.x == OverflowAxis::Scroll {
+                            (max.x, offset_x)
+                        } else {
+                            (max.y, offset_y)
+                        };
+                        let near_end = max - offset.clamp(0., max) <= threshold;
+                        if !near_end || (max - state.max).abs() > SCROLL_LIMIT_EPSILON {
+                            state.fired = false;
+                        }
+                        state.max = max;
+                        if near_end && !state.fired {
+                            state.fired = true;
+                            commands.run_system_with(system, entity);
+                        }
+                    }
+                });
+            }))
+            .apply(remove_system_holder_on_remove(system_holder))
+        })
+    }
+
+    /// When this element's viewport comes within `threshold` logical pixels of the end of its
+    /// contents, run a function, see
+    /// [`.on_scroll_near_end_with_system`](ViewportMutable::on_scroll_near_end_with_system).
+    fn on_scroll_near_end(self, threshold: f32, mut handler: impl FnMut() + Send + Sync + 'static) -> Self {
+        self.on_scroll_near_end_with_system(threshold, move |_: In<Entity>| handler())
+    }
+
+    /// When this element's viewport comes within `threshold` logical pixels of the end of its
+    /// contents, spawn the [`Future`] returned by `load`, e.g. one fetching and appending the next
+    /// page of items. `loading` is `true` while the [`Future`] is running, during which further
+    /// crossings are ignored; derive a spinner item from its signal to show that more is on the
+    /// way.
+    fn on_scroll_near_end_load<F: Future<Output = ()> + Send + 'static>(
+        self,
+        threshold: f32,
+        loading: Mutable<bool>,
+        mut load: impl FnMut() -> F + Send + Sync + 'static,
+    ) -> Self {
+        self.on_scroll_near_end(threshold, move || {
+            if !loading.replace(true) {
+                let load = load();
+                spawn(clone!((loading) async move {
+                    load.await;
+                    loading.set(false);
+                }))
+                .detach();
+            }
+        })
+    }
+
+    /// Sync a [`Mutable<Option<Entity>>`] with this element's [`Entity`], e.g. to bind a
+    /// [`Scrollbar`](super::scrollbar::Scrollbar) to it; it is reset to [`None`] when this element
+    /// is despawned.
+    fn viewport_entity_sync(self, entity_option: Mutable<Option<Entity>>) -> Self {
+        self.update_raw_el(|raw_el| {
+            raw_el
+                .on_spawn(clone!((entity_option) move |_, entity| entity_option.set(Some(entity))))
+                .on_remove(move |_, entity| {
+                    if entity_option.get() == Some(entity) {
+                        entity_option.set(None);
+                    }
+                })
+        })
+    }
+
     /// Reactively set the horizontal position of the viewport.
     fn viewport_x_signal<S: Signal<Item = f32> + Send + 'static>(
         mut self,
@@ -206,26 +546,38 @@ pub trait ViewportMutable: RawElWrapper {
     }
 }
 
-/// Use to fetch the logical pixel coordinates of the UI node, based on its [`GlobalTransform`].
+/// Use to fetch the physical pixel coordinates of the UI node, based on its [`GlobalTransform`].
 #[derive(SystemParam)]
-pub struct LogicalRect<'w, 's> {
+pub struct PhysicalRect<'w, 's> {
     data: Query<'w, 's, (&'static ComputedNode, &'static GlobalTransform)>,
 }
 
-impl LogicalRect<'_, '_> {
-    /// Get the logical pixel coordinates of the UI node, based on its [`GlobalTransform`].
+impl PhysicalRect<'_, '_> {
+    /// Get the physical pixel coordinates of the UI node, based on its [`GlobalTransform`].
     pub fn get(&self, entity: Entity) -> Option<Rect> {
         if let Ok((computed_node, global_transform)) = self.data.get(entity) {
             return Rect::from_center_size(global_transform.translation().xy(), computed_node.size()).apply(Some);
         }
         None
     }
+
+    /// Get the UI node's coordinates in logical pixels, accounting for [`UiScale`], i.e. in the same
+    /// units as [`Val::Px`].
+    pub fn logical(&self, entity: Entity) -> Option<Rect> {
+        let (computed_node, global_transform) = self.data.get(entity).ok()?;
+        let inverse_scale_factor = computed_node.inverse_scale_factor();
+        Rect::from_center_size(
+            global_transform.translation().xy() * inverse_scale_factor,
+            computed_node.size() * inverse_scale_factor,
+        )
+        .apply(Some)
+    }
 }
 
 #[derive(SystemParam)]
 struct SceneViewport<'w, 's> {
     childrens: Query<'w, 's, &'static Children>,
-    logical_rect: LogicalRect<'w, 's>,
+    physical_rect: PhysicalRect<'w, 's>,
     scroll_positions: Query<'w, 's, &'static ScrollPosition>,
 }
 
@@ -234,7 +586,7 @@ impl SceneViewport<'_, '_> {
         if let Some(Vec2 {
             x: viewport_width,
             y: viewport_height,
-        }) = self.logical_rect.get(entity).as_ref().map(Rect::size)
+        }) = self.physical_rect.get(entity).as_ref().map(Rect::size)
             && let Ok(&ScrollPosition { offset_x, offset_y }) = self.scroll_positions.get(entity)
         {
             let mut min = Vec2::MAX;
@@ -246,7 +598,7 @@ impl SceneViewport<'_, '_> {
                 .into_iter()
                 .flat_map(|children| children.iter())
             {
-                if let Some(child_rect) = self.logical_rect.get(child) {
+                if let Some(child_rect) = self.physical_rect.get(child) {
                     min = min.min(child_rect.min);
                     max = max.max(child_rect.max);
                 }
@@ -311,9 +663,307 @@ fn viewport_location_change_dispatcher(
     }
 }
 
+// the offset along one axis which places the `child` span within the `view` span according to
+// `alignment`, given the viewport's current offset `position`
+fn align(
+    alignment: ScrollAlignment,
+    position: f32,
+    (child_min, child_max): (f32, f32),
+    (view_min, view_max): (f32, f32),
+) -> f32 {
+    let start = position + child_min - view_min;
+    let end = position + child_max - view_max;
+    match alignment {
+        ScrollAlignment::Start => start,
+        ScrollAlignment::Center => (start + end) / 2.,
+        ScrollAlignment::End => end,
+        ScrollAlignment::Nearest => {
+            // children larger than the viewport show their start
+            if child_min < view_min || child_max - child_min > view_max - view_min {
+                start
+            } else if child_max > view_max {
+                end
+            } else {
+                position
+            }
+        }
+    }
+}
+
+fn apply_pending_scrolls(
+    mut pending_scrolls: Query<(Entity, &mut PendingScroll)>,
+    mut viewports: Query<(&mut ScrollPosition, &ComputedNode, &Node)>,
+    physical_rect: PhysicalRect,
+    mut commands: Commands,
+) {
+    for (entity, mut pending_scroll) in pending_scrolls.iter_mut() {
+        let PendingScroll { target, mode, frames } = *pending_scroll;
+        // keep waiting for layout, unless it has had long enough
+        let mut wait = || {
+            if frames + 1 < PENDING_SCROLL_FRAMES {
+                pending_scroll.frames += 1;
+            } else {
+                commands.entity(entity).remove::<PendingScroll>();
+            }
+        };
+        let Ok((mut scroll_position, computed_node, node)) = viewports.get_mut(entity) else {
+            wait();
+            continue;
+        };
+        if computed_node.is_empty() {
+            wait();
+            continue;
+        }
+        let position = Vec2::new(scroll_position.offset_x, scroll_position.offset_y);
+        let target = match target {
+            ScrollTarget::Offset(offset) => offset,
+            ScrollTarget::Child(child, alignment) => {
+                let (Some(viewport_rect), Some(child_rect)) = (physical_rect.get(entity), physical_rect.get(child))
+                else {
+                    wait();
+                    continue;
+                };
+                if child_rect.is_empty() {
+                    wait();
+                    continue;
+                }
+                // `ComputedNode` and `GlobalTransform` are in physical pixels
+                let inverse_scale_factor = computed_node.inverse_scale_factor();
+                let inset = computed_node.content_inset();
+                let view_min = (viewport_rect.min + Vec2::new(inset.left, inset.top)) * inverse_scale_factor;
+                let view_max = (viewport_rect.max - Vec2::new(inset.right, inset.bottom)) * inverse_scale_factor;
+                let child_min = child_rect.min * inverse_scale_factor;
+                let child_max = child_rect.max * inverse_scale_factor;
+                Vec2::new(
+                    align(
+                        alignment,
+                        position.x,
+                        (child_min.x, child_max.x),
+                        (view_min.x, view_max.x),
+                    ),
+                    align(
+                        alignment,
+                        position.y,
+                        (child_min.y, child_max.y),
+                        (view_min.y, view_max.y),
+                    ),
+                )
+            }
+        };
+        commands.entity(entity).remove::<PendingScroll>();
+        let mut target = target.clamp(Vec2::ZERO, max_scroll_offset(computed_node));
+        // layout resets offsets along axes which do not scroll
+        if node.overflow.x != OverflowAxis::Scroll {
+            target.x = position.x;
+        }
+        if node.overflow.y != OverflowAxis::Scroll {
+            target.y = position.y;
+        }
+        if target != position {
+            scroll_to_target(entity, &mut scroll_position, target, mode, &mut commands);
+        }
+    }
+}
+
+// the start and end, along `edge`'s axis, of the rect centered at `center` with `size`, shrunk by
+// `inset`
+fn span(edge: StickyEdge, center: Vec2, size: Vec2, inset: Option<BorderRect>) -> (f32, f32) {
+    let inset = inset.unwrap_or_default();
+    if edge.is_vertical() {
+        (
+            center.y - size.y / 2. + inset.top,
+            center.y + size.y / 2. - inset.bottom,
+        )
+    } else {
+        (
+            center.x - size.x / 2. + inset.left,
+            center.x + size.x / 2. - inset.right,
+        )
+    }
+}
+
+// after layout positions sticky elements, shift them into their viewport's visible rect; layout
+// resets them on the next frame, so the shift always reflects the latest scroll position
+fn position_sticky_elements(
+    stickies: Query<(Entity, &Sticky, &ChildOf)>,
+    child_ofs: Query<&ChildOf>,
+    childrens: Query<&Children>,
+    computed_nodes: Query<(&ComputedNode, Option<&Sticky>)>,
+    mutable_viewports: Query<(), With<MutableViewport>>,
+    mut transforms: Query<&mut Transform>,
+) {
+    // `Transform`s of UI nodes are relative to their parent's center, in physical pixels
+    let translation = |transforms: &Query<&mut Transform>, entity| {
+        transforms
+            .get(entity)
+            .map(|transform| transform.translation.truncate())
+            .unwrap_or_default()
+    };
+    let mut shifts = vec![];
+    for (entity, &Sticky(edge), &ChildOf(parent)) in stickies.iter() {
+        // centers relative to the viewport's center
+        let mut center = translation(&transforms, entity);
+        let mut viewport_option = None;
+        for ancestor in child_ofs.iter_ancestors(entity) {
+            if mutable_viewports.contains(ancestor) {
+                viewport_option = Some(ancestor);
+                break;
+            }
+            center += translation(&transforms, ancestor);
+        }
+        let Some(viewport) = viewport_option else {
+            continue;
+        };
+        let (Ok((computed_node, _)), Ok((viewport_node, _))) =
+            (computed_nodes.get(entity), computed_nodes.get(viewport))
+        else {
+            continue;
+        };
+        let parent_center = center - translation(&transforms, entity);
+        let (start, end) = span(edge, center, computed_node.size(), None);
+        let (view_start, view_end) = span(
+            edge,
+            Vec2::ZERO,
+            viewport_node.size(),
+            Some(viewport_node.content_inset()),
+        );
+        let (mut bound_start, mut bound_end) = (f32::NEG_INFINITY, f32::INFINITY);
+        if parent != viewport
+            && let Ok((parent_node, _)) = computed_nodes.get(parent)
+        {
+            (bound_start, bound_end) = span(
+                edge,
+                parent_center,
+                parent_node.size(),
+                Some(parent_node.content_inset()),
+            );
+        }
+        // the next sibling pinned to the same edge pushes this one out
+        if let Ok(siblings) = childrens.get(parent) {
+            let index = siblings
+                .iter()
+                .position(|sibling| sibling == entity)
+                .unwrap_or_default();
+            let (before, after) = siblings.split_at(index);
+            let sticky_sibling = |sibling: &Entity| {
+                computed_nodes
+                    .get(*sibling)
+                    .ok()
+                    .filter(|(_, sticky)| sticky.map(|sticky| sticky.0) == Some(edge))
+                    .map(|(node, _)| {
+                        span(
+                            edge,
+                            parent_center + translation(&transforms, *sibling),
+                            node.size(),
+                            None,
+                        )
+                    })
+            };
+            if edge.is_start() {
+                if let Some((next_start, _)) = after.iter().skip(1).find_map(sticky_sibling) {
+                    bound_end = bound_end.min(next_start);
+                }
+            } else if let Some((_, previous_end)) = before.iter().rev().find_map(sticky_sibling) {
+                bound_start = bound_start.max(previous_end);
+            }
+        }
+        let shift = if edge.is_start() {
+            (view_start - start).max(0.).min((bound_end - end).max(0.))
+        } else {
+            (view_end - end).min(0.).max((bound_start - start).min(0.))
+        };
+        if shift != 0. {
+            shifts.push((
+                entity,
+                if edge.is_vertical() {
+                    Vec2::new(0., shift)
+                } else {
+                    Vec2::new(shift, 0.)
+                },
+            ));
+        }
+    }
+    for (entity, shift) in shifts {
+        if let Ok(mut transform) = transforms.get_mut(entity) {
+            transform.translation += shift.extend(0.);
+        }
+    }
+}
+
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         Update,
         viewport_location_change_dispatcher.run_if(any_with_component::<OnViewportLocationChange>),
+    )
+    // after layout and transform propagation, so just spawned children have rects
+    .add_systems(
+        PostUpdate,
+        apply_pending_scrolls
+            .after(TransformSystem::TransformPropagate)
+            .run_if(any_with_component::<PendingScroll>),
+    )
+    .add_systems(
+        PostUpdate,
+        position_sticky_elements
+            .after(UiSystem::Layout)
+            .before(apply_overscroll)
+            .before(TransformSystem::TransformPropagate)
+            .run_if(any_with_component::<Sticky>),
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_utils::prelude::*;
+
+    #[test]
+    fn pending_scrolls_wait_for_their_child_to_be_laid_out() {
+        let mut world = World::new();
+        let viewport = world
+            .spawn((
+                Node {
+                    overflow: Overflow::scroll_y(),
+                    ..default()
+                },
+                ComputedNode {
+                    size: Vec2::splat(100.),
+                    content_size: Vec2::new(100., 400.),
+                    inverse_scale_factor: 1.,
+                    ..default()
+                },
+                GlobalTransform::from_translation(Vec3::new(50., 50., 0.)),
+                ScrollPosition::default(),
+            ))
+            .id();
+        let child = world
+            .spawn((
+                ComputedNode::default(),
+                GlobalTransform::from_translation(Vec3::new(50., 250., 0.)),
+                ChildOf(viewport),
+            ))
+            .id();
+        let options = ScrollIntoViewOptions {
+            alignment: ScrollAlignment::Start,
+            mode: ScrollMode::Instant,
+        };
+        world.commands().entity(viewport).scroll_into_view(child, options);
+        world.flush();
+        world.run_system_once(apply_pending_scrolls).unwrap();
+        assert!(world.get::<PendingScroll>(viewport).is_some());
+        assert_eq!(world.get::<ScrollPosition>(viewport).unwrap().offset_y, 0.);
+        world.get_mut::<ComputedNode>(child).unwrap().size = Vec2::splat(100.);
+        world.run_system_once(apply_pending_scrolls).unwrap();
+        assert!(world.get::<PendingScroll>(viewport).is_none());
+        assert_eq!(world.get::<ScrollPosition>(viewport).unwrap().offset_y, 200.);
+        // children which are never laid out are eventually given up on
+        world.get_mut::<ComputedNode>(child).unwrap().size = Vec2::ZERO;
+        world.commands().entity(viewport).scroll_into_view(child, options);
+        world.flush();
+        for _ in 0..PENDING_SCROLL_FRAMES {
+            world.run_system_once(apply_pending_scrolls).unwrap();
+        }
+        assert!(world.get::<PendingScroll>(viewport).is_none());
+    }
+}
@@ -0,0 +1,609 @@
+//! Virtualized [`Element`](super::element::Element)s which only spawn the items of large lists that
+//! are within their viewport, see [`VirtualColumn`] and [`VirtualGrid`].
+
+use std::sync::{Arc, Mutex};
+
+use apply::Apply;
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_math::prelude::*;
+use bevy_render::prelude::*;
+use bevy_ui::{UiSystem, prelude::*};
+use futures_signals::{
+    signal::{Mutable, ReadOnlyMutable, Signal, SignalExt},
+    signal_vec::{MutableVec, SignalVec, SignalVecExt, VecDiff},
+};
+
+use crate::impl_haalka_methods;
+
+use super::{
+    el::El,
+    element::{ElementWrapper, IntoOptionElement, Nameable, UiRootable},
+    global_event_aware::GlobalEventAware,
+    mouse_wheel_scrollable::MouseWheelScrollable,
+    pointer_event_aware::{CursorOnHoverable, PointerEventAware},
+    raw::RawElWrapper,
+    utils::{clone, spawn},
+    viewport_mutable::{Axis, Scene, Viewport, ViewportMutable},
+};
+use paste::paste;
+
+// how far, in logical pixels, beyond the viewport items are spawned by default
+const DEFAULT_OVERSCAN: f32 = 200.;
+// measured sizes, in logical pixels, closer than this to the cached size are ignored
+const MEASUREMENT_EPSILON: f32 = 0.5;
+
+/// How the heights of a [`VirtualColumn`]'s items are determined.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ItemSize {
+    /// Every item is this tall, in logical pixels.
+    Fixed(f32),
+    /// Items are measured once spawned and their heights are cached, while items which have not
+    /// been measured yet are assumed to be `estimate` logical pixels tall.
+    Measured {
+        #[allow(missing_docs)]
+        estimate: f32,
+    },
+}
+
+#[derive(Clone, Copy)]
+enum Layout {
+    Column(ItemSize),
+    // fixed cell size, in logical pixels
+    Grid(Vec2),
+}
+
+/// Spawned item which is recycled to display whichever item of the list it is assigned.
+struct Slot<T> {
+    index: Mutable<usize>,
+    value: Mutable<T>,
+    // in logical pixels, relative to the start of the list
+    offset: Mutable<Vec2>,
+}
+
+impl<T> Clone for Slot<T> {
+    fn clone(&self) -> Self {
+        Self {
+            index: self.index.clone(),
+            value: self.value.clone(),
+            offset: self.offset.clone(),
+        }
+    }
+}
+
+struct State<T> {
+    items: Vec<T>,
+    // measured heights of the items of `ItemSize::Measured` columns
+    sizes: Vec<Option<f32>>,
+    layout: Layout,
+    // the viewport's offset and extent along the scrolling axis and its extent along the cross axis,
+    // in logical pixels
+    offset: f32,
+    extent: f32,
+    cross_extent: f32,
+    // the number of slots, which only grows for measured items so slots aren't reassigned wholesale
+    // as differently sized items scroll by
+    capacity: usize,
+}
+
+fn apply_diff<T: Clone>(items: &mut Vec<T>, sizes: &mut Vec<Option<f32>>, diff: VecDiff<T>) {
+    match diff {
+        VecDiff::Replace { values } => {
+            sizes.clear();
+            sizes.resize(values.len(), None);
+            *items = values;
+        }
+        VecDiff::InsertAt { index, value } => {
+            items.insert(index, value);
+            sizes.insert(index, None);
+        }
+        VecDiff::UpdateAt { index, value } => {
+            items[index] = value;
+            sizes[index] = None;
+        }
+        VecDiff::RemoveAt { index } => {
+            items.remove(index);
+            sizes.remove(index);
+        }
+        VecDiff::Move { old_index, new_index } => {
+            let item = items.remove(old_index);
+            items.insert(new_index, item);
+            let size = sizes.remove(old_index);
+            sizes.insert(new_index, size);
+        }
+        VecDiff::Push { value } => {
+            items.push(value);
+            sizes.push(None);
+        }
+        VecDiff::Pop {} => {
+            items.pop();
+            sizes.pop();
+        }
+        VecDiff::Clear {} => {
+            items.clear();
+            sizes.clear();
+        }
+    }
+}
+
+/// Assigns the items within the viewport to a recycled pool of [`Slot`]s.
+struct Virtualizer<T> {
+    state: Arc<Mutex<State<T>>>,
+    slots: MutableVec<Slot<T>>,
+    overscan: Mutable<f32>,
+    // length of all the items along the scrolling axis, in logical pixels
+    content_extent: Mutable<f32>,
+}
+
+impl<T> Clone for Virtualizer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            slots: self.slots.clone(),
+            overscan: self.overscan.clone(),
+            content_extent: self.content_extent.clone(),
+        }
+    }
+}
+
+impl<T: Clone> Virtualizer<T> {
+    fn new(layout: Layout, overscan: Mutable<f32>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                items: vec![],
+                sizes: vec![],
+                layout,
+                offset: 0.,
+                extent: 0.,
+                cross_extent: 0.,
+                capacity: 0,
+            })),
+            slots: MutableVec::new(),
+            overscan,
+            content_extent: Mutable::new(0.),
+        }
+    }
+
+    fn apply_diff(&self, diff: VecDiff<T>) {
+        {
+            let mut state = self.state.lock().unwrap();
+            let State { items, sizes, .. } = &mut *state;
+            apply_diff(items, sizes, diff);
+        }
+        self.refresh(true);
+    }
+
+    fn set_viewport(&self, offset: f32, extent: f32, cross_extent: f32) {
+        {
+            let mut state = self.state.lock().unwrap();
+            if (state.offset, state.extent, state.cross_extent) == (offset, extent, cross_extent) {
+                return;
+            }
+            state.offset = offset;
+            state.extent = extent;
+            state.cross_extent = cross_extent;
+        }
+        self.refresh(false);
+    }
+
+    fn measure(&self, index: usize, size: f32) {
+        if size <= 0. {
+            return;
+        }
+        {
+            let mut state = self.state.lock().unwrap();
+            match state.sizes.get_mut(index) {
+                Some(Some(cached)) if (*cached - size).abs() < MEASUREMENT_EPSILON => return,
+                Some(cached) => *cached = Some(size),
+                None => return,
+            }
+        }
+        self.refresh(false);
+    }
+
+    // reassign the slots to the items within the viewport plus the overscan, refreshing their
+    // values if `values_changed`
+    fn refresh(&self, values_changed: bool) {
+        let mut state = self.state.lock().unwrap();
+        let len = state.items.len();
+        let overscan = self.overscan.get();
+        let start = state.offset - overscan;
+        let end = state.offset + state.extent + overscan;
+        let (first, needed, content_extent, position): (usize, usize, f32, Box<dyn Fn(usize) -> Vec2>) =
+            match state.layout {
+                Layout::Column(ItemSize::Fixed(height)) => {
+                    let height = height.max(1.);
+                    (
+                        (start / height).floor().max(0.) as usize,
+                        ((end - start) / height).ceil() as usize + 1,
+                        len as f32 * height,
+                        Box::new(move |index| Vec2::new(0., index as f32 * height)),
+                    )
+                }
+                Layout::Column(ItemSize::Measured { estimate }) => {
+                    let mut offsets = Vec::with_capacity(len + 1);
+                    let mut offset = 0.;
+                    offsets.push(offset);
+                    for size in state.sizes.iter() {
+                        offset += size.unwrap_or(estimate);
+                        offsets.push(offset);
+                    }
+                    // the first item ending after `start` and the first item starting at or after `end`
+                    let first = offsets[1..].partition_point(|&item_end| item_end <= start);
+                    let last = offsets[..len].partition_point(|&item_start| item_start < end);
+                    (
+                        first,
+                        last.saturating_sub(first) + 1,
+                        offsets[len],
+                        Box::new(move |index| Vec2::new(0., offsets[index])),
+                    )
+                }
+                Layout::Grid(cell) => {
+                    let cell = cell.max(Vec2::ONE);
+                    let columns = ((state.cross_extent / cell.x).floor() as usize).max(1);
+                    (
+                        (start / cell.y).floor().max(0.) as usize * columns,
+                        (((end - start) / cell.y).ceil() as usize + 1) * columns,
+                        len.div_ceil(columns) as f32 * cell.y,
+                        Box::new(move |index| {
+                            Vec2::new((index % columns) as f32 * cell.x, (index / columns) as f32 * cell.y)
+                        }),
+                    )
+                }
+            };
+        state.capacity = match state.layout {
+            Layout::Column(ItemSize::Measured { .. }) => state.capacity.max(needed),
+            _ => needed,
+        };
+        let count = state.capacity.min(len);
+        let first = first.min(len - count);
+        let mut slots = self.slots.lock_mut();
+        while slots.len() > count {
+            slots.pop();
+        }
+        // each item is displayed by the slot at its index modulo the number of slots, so scrolling
+        // by one item reassigns one slot
+        let mut assignments = (first..first + count).collect::<Vec<_>>();
+        assignments.sort_by_key(|index| index % count);
+        for (slot_index, index) in assignments.into_iter().enumerate() {
+            let value = &state.items[index];
+            if let Some(slot) = slots.get(slot_index) {
+                if values_changed || slot.index.get() != index {
+                    slot.value.set(value.clone());
+                }
+                slot.index.set_neq(index);
+                slot.offset.set_neq(position(index));
+            } else {
+                slots.push_cloned(Slot {
+                    index: Mutable::new(index),
+                    value: Mutable::new(value.clone()),
+                    offset: Mutable::new(position(index)),
+                });
+            }
+        }
+        self.content_extent.set_neq(content_extent);
+    }
+}
+
+/// Reports the size of a spawned item of an [`ItemSize::Measured`] [`VirtualColumn`].
+#[derive(Component)]
+struct MeasuredItem {
+    // the index of the item the spawned item displays, which lags its slot's index until the
+    // reassignment reaches the world
+    index: usize,
+    measure: Arc<dyn Fn(usize, f32) + Send + Sync>,
+}
+
+fn virtualized<T, IOE>(
+    items: impl SignalVec<Item = T> + Send + 'static,
+    layout: Layout,
+    overscan: Mutable<f32>,
+    mut item: impl FnMut(ReadOnlyMutable<usize>, ReadOnlyMutable<T>) -> IOE + Send + 'static,
+) -> El<Node>
+where
+    T: Clone + Send + Sync + 'static,
+    IOE: IntoOptionElement + 'static,
+{
+    let virtualizer = Virtualizer::new(layout, overscan.clone());
+    let updater = items
+        .for_each(clone!((virtualizer) move |diff| {
+            virtualizer.apply_diff(diff);
+            async {}
+        }))
+        .apply(spawn);
+    let overscan_updater = overscan
+        .signal()
+        .for_each(clone!((virtualizer) move |_| {
+            virtualizer.refresh(false);
+            async {}
+        }))
+        .apply(spawn);
+    let content = El::<Node>::new()
+        .with_node(|mut node| {
+            node.width = Val::Percent(100.);
+            node.flex_shrink = 0.;
+        })
+        .on_signal_with_node(virtualizer.content_extent.signal(), |mut node, extent| {
+            node.height = Val::Px(extent)
+        })
+        .update_raw_el(clone!((virtualizer) move |raw_el| {
+            raw_el.children_signal_vec(virtualizer.slots.signal_vec_cloned().map(clone!((virtualizer) move |slot: Slot<T>| {
+                let item = item(slot.index.read_only(), slot.value.read_only());
+                El::<Node>::new()
+                    .with_node(move |mut node| {
+                        node.position_type = PositionType::Absolute;
+                        match layout {
+                            Layout::Column(item_size) => {
+                                node.width = Val::Percent(100.);
+                                if let ItemSize::Fixed(height) = item_size {
+                                    node.height = Val::Px(height);
+                                }
+                            }
+                            Layout::Grid(cell) => {
+                                node.width = Val::Px(cell.x);
+                                node.height = Val::Px(cell.y);
+                            }
+                        }
+                    })
+                    .on_signal_with_node(slot.offset.signal(), |mut node, offset| {
+                        node.left = Val::Px(offset.x);
+                        node.top = Val::Px(offset.y);
+                    })
+                    .apply(|el| {
+                        if let Layout::Column(ItemSize::Measured { .. }) = layout {
+                            el.update_raw_el(clone!((virtualizer, slot) move |raw_el| {
+                                raw_el
+                                    .insert(MeasuredItem {
+                                        index: slot.index.get(),
+                                        measure: Arc::new(move |index, size| virtualizer.measure(index, size)),
+                                    })
+                                    .on_signal_with_component::<_, MeasuredItem>(slot.index.signal(), |mut measured_item, index| {
+                                        measured_item.index = index
+                                    })
+                            }))
+                        } else {
+                            el
+                        }
+                    })
+                    .child(item)
+            })))
+        }));
+    El::<Node>::new()
+        .mutable_viewport(Axis::Vertical)
+        .on_viewport_location_change_with_system(
+            move |In((entity, _)): In<(Entity, (Scene, Viewport))>,
+                  viewports: Query<(&ComputedNode, &ScrollPosition)>| {
+                if let Ok((computed_node, scroll_position)) = viewports.get(entity) {
+                    // `ComputedNode` is in physical pixels
+                    let size = computed_node.size() * computed_node.inverse_scale_factor();
+                    virtualizer.set_viewport(scroll_position.offset_y, size.y, size.x);
+                }
+            },
+        )
+        .update_raw_el(|raw_el| raw_el.hold_tasks([updater, overscan_updater]))
+        .child(content)
+}
+
+/// Vertically scrollable list which only spawns the items of its [`SignalVec`] that are within its
+/// viewport plus an overscan margin, recycling the spawned items as it is scrolled; suited to
+/// thousands of items, e.g. logs, chats, or leaderboards. Spawned items are passed the index and
+/// value they currently display, which change as they are recycled, so they should be rendered
+/// reactively from them.
+///
+/// Like other [`ViewportMutable`] elements, scrolling input must be enabled separately, e.g. with
+/// [`.on_scroll_with_system_on_hover`](super::mouse_wheel_scrollable::OnHoverMouseWheelScrollable::on_scroll_with_system_on_hover).
+pub struct VirtualColumn {
+    el: El<Node>,
+    overscan: Mutable<f32>,
+}
+
+impl VirtualColumn {
+    /// Construct a [`VirtualColumn`] of `items`, each rendered by `item`, see [`ItemSize`].
+    pub fn new<T, IOE>(
+        items: impl SignalVec<Item = T> + Send + 'static,
+        item_size: ItemSize,
+        item: impl FnMut(ReadOnlyMutable<usize>, ReadOnlyMutable<T>) -> IOE + Send + 'static,
+    ) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+        IOE: IntoOptionElement + 'static,
+    {
+        let overscan = Mutable::new(DEFAULT_OVERSCAN);
+        Self {
+            el: virtualized(items, Layout::Column(item_size), overscan.clone(), item),
+            overscan,
+        }
+    }
+
+    /// Set how far, in logical pixels, beyond the viewport items are spawned.
+    pub fn overscan(self, overscan: f32) -> Self {
+        self.overscan.set(overscan);
+        self
+    }
+}
+
+/// Vertically scrollable grid of fixed size cells which only spawns the items of its [`SignalVec`]
+/// that are within its viewport plus an overscan margin, recycling the spawned cells as it is
+/// scrolled; suited to large inventories or galleries. As many columns as fit the viewport's width
+/// are laid out. Spawned cells are passed the index and value they currently display, see
+/// [`VirtualColumn`].
+pub struct VirtualGrid {
+    el: El<Node>,
+    overscan: Mutable<f32>,
+}
+
+impl VirtualGrid {
+    /// Construct a [`VirtualGrid`] of `items`, each rendered by `item` in a cell of `cell_size`
+    /// logical pixels.
+    pub fn new<T, IOE>(
+        items: impl SignalVec<Item = T> + Send + 'static,
+        cell_size: Vec2,
+        item: impl FnMut(ReadOnlyMutable<usize>, ReadOnlyMutable<T>) -> IOE + Send + 'static,
+    ) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+        IOE: IntoOptionElement + 'static,
+    {
+        let overscan = Mutable::new(DEFAULT_OVERSCAN);
+        Self {
+            el: virtualized(items, Layout::Grid(cell_size), overscan.clone(), item),
+            overscan,
+        }
+    }
+
+    /// Set how far, in logical pixels, beyond the viewport cells are spawned.
+    pub fn overscan(self, overscan: f32) -> Self {
+        self.overscan.set(overscan);
+        self
+    }
+}
+
+macro_rules! impl_virtualized {
+    ($($el_type:ty),* $(,)?) => {
+        $(
+            impl ElementWrapper for $el_type {
+                type EL = El<Node>;
+                fn element_mut(&mut self) -> &mut Self::EL {
+                    &mut self.el
+                }
+            }
+
+            impl CursorOnHoverable for $el_type {}
+            impl GlobalEventAware for $el_type {}
+            impl MouseWheelScrollable for $el_type {}
+            impl Nameable for $el_type {}
+            impl PointerEventAware for $el_type {}
+            impl UiRootable for $el_type {}
+            impl ViewportMutable for $el_type {}
+
+            impl_haalka_methods! {
+                $el_type {
+                    node: Node,
+                    background_color: BackgroundColor,
+                    border_color: BorderColor,
+                    border_radius: BorderRadius,
+                    scroll_position: ScrollPosition,
+                    visibility: Visibility,
+                }
+            }
+        )*
+    };
+}
+
+impl_virtualized!(VirtualColumn, VirtualGrid);
+
+fn measure_virtual_items(items: Query<(Ref<MeasuredItem>, &ComputedNode)>) {
+    for (measured_item, computed_node) in items.iter() {
+        // an item reassigned this frame may have been laid out with its previous contents
+        if measured_item.is_changed() {
+            continue;
+        }
+        (measured_item.measure)(
+            measured_item.index,
+            computed_node.size().y * computed_node.inverse_scale_factor(),
+        );
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        PostUpdate,
+        measure_virtual_items
+            .after(UiSystem::Layout)
+            .run_if(any_with_component::<MeasuredItem>),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the indices and offsets the slots display, by index
+    fn assigned(virtualizer: &Virtualizer<usize>) -> Vec<(usize, Vec2)> {
+        let mut assigned = virtualizer
+            .slots
+            .lock_ref()
+            .iter()
+            .map(|slot| (slot.index.get(), slot.offset.get()))
+            .collect::<Vec<_>>();
+        assigned.sort_by_key(|&(index, _)| index);
+        assigned
+    }
+
+    fn virtualizer(layout: Layout, len: usize) -> Virtualizer<usize> {
+        let virtualizer = Virtualizer::new(layout, Mutable::new(0.));
+        virtualizer.apply_diff(VecDiff::Replace {
+            values: (0..len).collect(),
+        });
+        virtualizer
+    }
+
+    #[test]
+    fn fixed_column_assigns_the_visible_items() {
+        let virtualizer = virtualizer(Layout::Column(ItemSize::Fixed(10.)), 100);
+        virtualizer.set_viewport(55., 30., 100.);
+        assert_eq!(
+            assigned(&virtualizer),
+            (5..9).map(|index| (index, Vec2::new(0., index as f32 * 10.))).collect::<Vec<_>>()
+        );
+        assert_eq!(virtualizer.content_extent.get(), 1000.);
+        // scrolling by one item reassigns a single slot
+        let before = virtualizer.slots.lock_ref().iter().map(|slot| slot.index.get()).collect::<Vec<_>>();
+        virtualizer.set_viewport(65., 30., 100.);
+        let after = virtualizer.slots.lock_ref().iter().map(|slot| slot.index.get()).collect::<Vec<_>>();
+        assert_eq!(before.iter().zip(&after).filter(|(before, after)| before != after).count(), 1);
+        assert_eq!(assigned(&virtualizer).first().map(|&(index, _)| index), Some(6));
+        // the last items stay assigned when scrolled past the end
+        virtualizer.set_viewport(2000., 30., 100.);
+        assert_eq!(
+            assigned(&virtualizer).iter().map(|&(index, _)| index).collect::<Vec<_>>(),
+            [96, 97, 98, 99]
+        );
+    }
+
+    #[test]
+    fn refresh_follows_the_overscan() {
+        let virtualizer = virtualizer(Layout::Column(ItemSize::Fixed(10.)), 100);
+        virtualizer.set_viewport(50., 30., 100.);
+        assert_eq!(assigned(&virtualizer).len(), 4);
+        virtualizer.overscan.set(20.);
+        virtualizer.refresh(false);
+        assert_eq!(
+            assigned(&virtualizer).iter().map(|&(index, _)| index).collect::<Vec<_>>(),
+            (3..11).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn measured_column_positions_items_by_their_sizes() {
+        let virtualizer = virtualizer(Layout::Column(ItemSize::Measured { estimate: 10. }), 10);
+        virtualizer.set_viewport(0., 100., 100.);
+        virtualizer.measure(0, 30.);
+        assert_eq!(virtualizer.content_extent.get(), 120.);
+        assert_eq!(assigned(&virtualizer)[1], (1, Vec2::new(0., 30.)));
+        // a moved item keeps its size
+        virtualizer.apply_diff(VecDiff::Move {
+            old_index: 0,
+            new_index: 1,
+        });
+        assert_eq!(assigned(&virtualizer)[1], (1, Vec2::new(0., 10.)));
+        assert!(virtualizer.slots.lock_ref().iter().any(|slot| (slot.index.get(), slot.value.get()) == (1, 0)));
+        assert_eq!(virtualizer.content_extent.get(), 120.);
+        // a replaced item is measured anew
+        virtualizer.apply_diff(VecDiff::UpdateAt { index: 1, value: 1 });
+        assert_eq!(virtualizer.content_extent.get(), 100.);
+    }
+
+    #[test]
+    fn grid_fits_columns_to_the_viewport() {
+        let virtualizer = virtualizer(Layout::Grid(Vec2::splat(10.)), 10);
+        virtualizer.set_viewport(0., 15., 35.);
+        assert_eq!(virtualizer.content_extent.get(), 40.);
+        assert_eq!(
+            assigned(&virtualizer),
+            (0..9)
+                .map(|index| (index, Vec2::new((index % 3) as f32 * 10., (index / 3) as f32 * 10.)))
+                .collect::<Vec<_>>()
+        );
+    }
+}